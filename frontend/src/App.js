@@ -40,6 +40,13 @@ function App() {
           await switchToPolygonAmoy();
           localStorage.setItem("smartbid-address", accounts[0]);
 
+          if (!apiService.hasSession(accounts[0])) {
+            const [signedIn, message] = await apiService.signIn(accounts[0]);
+            if (!signedIn) {
+              toast.error(`Sign in failed: ${message}`);
+            }
+          }
+
           const userAddress = accounts[0];
           const signalingManager = new SignalingManager(userAddress);
 
@@ -146,11 +153,9 @@ function App() {
     }
 
     async function processTransfer(itemId) {
-      const buyerAddress = localStorage.getItem("smartbid-address");
-
       toast.loading("Processing transfer...", { id: "transfer" });
 
-      const [success, data] = await apiService.transferItem(itemId, buyerAddress);
+      const [success, data] = await apiService.transferItem(itemId);
 
       if (success) {
         toast.success("Item will be shortly transferred to you", { id: "transfer" });
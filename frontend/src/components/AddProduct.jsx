@@ -2,6 +2,7 @@ import React, { useState } from "react";
 import { useNavigate } from "react-router-dom";
 import { motion } from "framer-motion";
 import apiService from "../utils/methods";
+import { toDecimalString } from "../utils/money";
 import { toast } from "react-hot-toast";
 
 const getUserItemsCache = () => {
@@ -129,10 +130,10 @@ const AddProduct = () => {
         description,
         images: base64Images,
         category,
-        base_price: parseFloat(basePrice),
+        base_price: toDecimalString(parseFloat(basePrice), "USD"),
       };
 
-      const [success, response] = await apiService.createItem(itemDetails, sellerAddress, auctionEndSeconds);
+      const [success, response] = await apiService.createItem(itemDetails, auctionEndSeconds);
 
       if (success) {
         toast.success("Your item will be up for auction soon!", { duration: 3000 });
@@ -2,6 +2,7 @@ import React, { useEffect, useState, useRef } from "react";
 import { useNavigate, useParams } from "react-router-dom";
 import { toast } from "react-hot-toast";
 import apiService from "../utils/methods";
+import { formatAmount, formatMoney, toAmount, toDecimalString } from "../utils/money";
 import { SignalingManager } from "../utils/SignalingManager";
 import LoadingSpinner from "./Loading";
 
@@ -123,7 +124,7 @@ const ItemDetail = () => {
       if (success) {
         setItem(data.item_details);
 
-        const numericBidPrice = Number(data.current_bid_price);
+        const numericBidPrice = data.current_bid_price == null ? -1 : toAmount(data.current_bid_price);
         setCurrentBidPrice(numericBidPrice);
         setSimilarItems(data.similar_items_details || []);
 
@@ -262,7 +263,7 @@ const ItemDetail = () => {
       }
     }
 
-    const [success, response] = await apiService.placeBid(id, incrementation, userAddress);
+    const [success, response] = await apiService.placeBid(id, toDecimalString(incrementation, item?.base_price?.currency));
 
     if (success) {
       toast.success("Bid placed successfully!");
@@ -270,7 +271,7 @@ const ItemDetail = () => {
       if (itemSuccess) {
         setItem(itemData.item_details);
 
-        const numericBidPrice = Number(itemData.current_bid_price);
+        const numericBidPrice = itemData.current_bid_price == null ? -1 : toAmount(itemData.current_bid_price);
         setCurrentBidPrice(numericBidPrice);
 
         if (!isNaN(numericBidPrice) && numericBidPrice !== -1) {
@@ -327,10 +328,10 @@ const ItemDetail = () => {
             <span className="font-semibold">Category:</span> {item.category}
           </p>
           <p className="text-lg font-semibold text-gray-800">
-            Base Price: <span className="text-gray-700">$ {item.base_price}</span>
+            Base Price: <span className="text-gray-700">{formatMoney(item.base_price)}</span>
           </p>
           <p className="text-lg font-semibold text-gray-800">
-            Current Price: <span className="text-green-600">{currentBidPrice !== -1 ? `$ ${currentBidPrice}` : "No bids yet"}</span>
+            Current Price: <span className="text-green-600">{currentBidPrice !== -1 ? formatAmount(currentBidPrice, item.base_price.currency) : "No bids yet"}</span>
           </p>
           <p className="text-lg font-semibold text-gray-700">Rating: ⭐ {convertRating(item.rating)}</p>
           <p className="text-lg font-semibold text-gray-700">
@@ -342,7 +343,7 @@ const ItemDetail = () => {
             <>
               {currentBidPrice === -1 ? (
                 <button className="bg-blue-500 hover:bg-blue-600 text-white px-6 py-3 rounded-lg font-semibold text-lg w-full transition-all" onClick={handleBidSubmit}>
-                  Place First Bid (${item.base_price})
+                  Place First Bid ({formatMoney(item.base_price)})
                 </button>
               ) : (
                 <>
@@ -376,7 +377,7 @@ const ItemDetail = () => {
                 <img src={item.images?.[0] || "placeholder.jpg"} alt={item.title} className="w-full h-40 object-contain rounded-md" />
                 <h2 className="text-xl font-bold mt-2 text-gray-800 capitalize">{item.title}</h2>
                 <p className="text-gray-600 mb-2 truncate capitalize">{item.description}</p>
-                <p className="text-lg font-semibold mb-2 text-gray-700">Price: ${item.base_price}</p>
+                <p className="text-lg font-semibold mb-2 text-gray-700">Price: {formatMoney(item.base_price)}</p>
                 <p className="text-lg font-semibold mb-4 text-gray-700">Status: {item.status}</p>
                 <button className="bg-blue-600 hover:bg-blue-700 hover:scale-105 text-white px-4 py-2 rounded w-full transition-all" onClick={() => navigate(`/auction/${item._id}`)}>
                   View Item
@@ -2,6 +2,7 @@ import React, { useEffect, useState } from "react";
 import { useNavigate } from "react-router-dom";
 import { motion, AnimatePresence } from "framer-motion";
 import apiService from "../utils/methods.js";
+import { formatMoney } from "../utils/money.js";
 import { toast } from "react-hot-toast";
 import LoadingSpinner from "./Loading.jsx";
 
@@ -74,7 +75,7 @@ const UserItems = () => {
     setShowConfirmation(false);
 
     try {
-      const [success, response] = await apiService.deleteItem(itemId, userAddress);
+      const [success, response] = await apiService.deleteItem(itemId);
 
       if (success) {
         toast.success("Item deletion initiated");
@@ -198,13 +199,13 @@ const UserItems = () => {
                 <div className="mt-auto space-y-2">
                   <div className="flex justify-between text-sm">
                     <span className="text-gray-500">Base Price</span>
-                    <span className="font-semibold">${item.base_price.toFixed(2)}</span>
+                    <span className="font-semibold">{formatMoney(item.base_price)}</span>
                   </div>
 
-                  {item.current_bid_price && item.current_bid_price > 0 && (
+                  {item.current_bid_price && (
                     <div className="flex justify-between text-sm">
                       <span className="text-gray-500">Current Bid</span>
-                      <span className="font-semibold text-blue-600">${item.current_bid_price.toFixed(2)}</span>
+                      <span className="font-semibold text-blue-600">{formatMoney(item.current_bid_price)}</span>
                     </div>
                   )}
 
@@ -257,7 +258,7 @@ const UserItems = () => {
                 Are you sure you want to delete <span className="font-semibold">{itemToDelete?.title}</span>? This action cannot be undone.
               </p>
 
-              {itemToDelete?.current_bid_price > 0 && (
+              {itemToDelete?.current_bid_price && (
                 <div className="bg-yellow-50 border-l-4 border-yellow-400 p-4 mb-6">
                   <div className="flex">
                     <div className="ml-3">
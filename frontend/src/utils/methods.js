@@ -1,22 +1,81 @@
 import axios from "axios";
+import { ethers } from "ethers";
 
 const API_BASE = "http://localhost:8080/api/v1";
+const SESSION_KEY = "smartbid-session";
+const SIWE_CHAIN_ID = 80002;
+
+// Requests carry the session token, the server takes the user's identity from it.
+const client = axios.create();
+client.interceptors.request.use((config) => {
+    const session = getSession();
+    if (session) {
+        config.headers.Authorization = `Bearer ${session.token}`;
+    }
+    return config;
+});
+
+function getSession() {
+    try {
+        const session = JSON.parse(localStorage.getItem(SESSION_KEY));
+        if (session && new Date(session.expires_at) > new Date()) return session;
+    } catch (error) {
+        localStorage.removeItem(SESSION_KEY);
+    }
+    return null;
+}
+
+function siweMessage(address, nonce, issuedAt) {
+    return [
+        `${window.location.host} wants you to sign in with your Ethereum account:`,
+        address,
+        "",
+        "Sign in to SmartBid.",
+        "",
+        `URI: ${window.location.origin}`,
+        "Version: 1",
+        `Chain ID: ${SIWE_CHAIN_ID}`,
+        `Nonce: ${nonce}`,
+        `Issued At: ${issuedAt}`,
+    ].join("\n");
+}
 
 const apiService = {
+    hasSession(address) {
+        const session = getSession();
+        return !!session && !!address && session.user_id === address.toLowerCase();
+    },
+
+    // Sign-In with Ethereum: the wallet signs a server issued nonce and the
+    // server answers with a session token.
+    async signIn(address) {
+        try {
+            const { data: nonce } = await client.get(`${API_BASE}/auth/nonce`);
+            const provider = new ethers.BrowserProvider(window.ethereum);
+            const signer = await provider.getSigner(address);
+            const message = siweMessage(ethers.getAddress(address), nonce.nonce, nonce.issued_at);
+            const signature = await signer.signMessage(message);
+            const { data } = await client.post(`${API_BASE}/auth/verify`, { message, signature });
+            localStorage.setItem(SESSION_KEY, JSON.stringify({ user_id: data.user_id, token: data.token, expires_at: data.expires_at }));
+            return [true, data];
+        } catch (error) {
+            return [false, error.response?.data?.message || "Failed to sign in."];
+        }
+    },
+
     async getItem(item_id) {
         try {
-            const response = await axios.get(`${API_BASE}/item`, { params: { item_id } });
+            const response = await client.get(`${API_BASE}/item`, { params: { item_id } });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch item."];
         }
     },
 
-    async createItem(itemDetails, seller, auction_end) {
+    async createItem(itemDetails, auction_end) {
         try {
-            const response = await axios.post(`${API_BASE}/item`, {
+            const response = await client.post(`${API_BASE}/item`, {
                 item_details: itemDetails,
-                seller,
                 auction_end,
             });
             return [true, response.data];
@@ -25,18 +84,18 @@ const apiService = {
         }
     },
 
-    async deleteItem(item_id, seller) {
+    async deleteItem(item_id) {
         try {
-            const response = await axios.delete(`${API_BASE}/item/${item_id}`, { data: { seller } });
+            const response = await client.delete(`${API_BASE}/item/${item_id}`);
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to delete item."];
         }
     },
 
-    async placeBid(item_id, incrementation, bidder) {
+    async placeBid(item_id, incrementation) {
         try {
-            const response = await axios.post(`${API_BASE}/place`, { item_id, incrementation, bidder });
+            const response = await client.post(`${API_BASE}/place`, { item_id, incrementation });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to place bid."];
@@ -45,16 +104,16 @@ const apiService = {
 
     async getHomePage() {
         try {
-            const response = await axios.get(`${API_BASE}/home`);
+            const response = await client.get(`${API_BASE}/home`);
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch home page data."];
         }
     },
 
-    async transferItem(item_id, buyer) {
+    async transferItem(item_id) {
         try {
-            const response = await axios.post(`${API_BASE}/transfer`, { item_id, buyer });
+            const response = await client.post(`${API_BASE}/transfer`, { item_id });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to transfer item."];
@@ -63,7 +122,7 @@ const apiService = {
 
     async getUserItems(user_id) {
         try {
-            const response = await axios.get(`${API_BASE}/userItems/${user_id}`);
+            const response = await client.get(`${API_BASE}/userItems/${user_id}`);
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch user items."];
@@ -72,7 +131,7 @@ const apiService = {
 
     async getOperationStatus(operation_id) {
         try {
-            const response = await axios.get(`${API_BASE}/status/operation/${operation_id}`);
+            const response = await client.get(`${API_BASE}/status/operation/${operation_id}`);
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch operation status."];
@@ -81,7 +140,7 @@ const apiService = {
 
     async getItemsByCategory(category_name) {
         try {
-            const response = await axios.get(`${API_BASE}/items/category/${category_name}`);
+            const response = await client.get(`${API_BASE}/items/category/${category_name}`);
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch category items."];
@@ -90,7 +149,7 @@ const apiService = {
 
     async getTopCategories(limit) {
         try {
-            const response = await axios.get(`${API_BASE}/categories/top`, { params: { limit } });
+            const response = await client.get(`${API_BASE}/categories/top`, { params: { limit } });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch top categories."];
@@ -99,7 +158,7 @@ const apiService = {
 
     async autocompleteSearch(query, limit) {
         try {
-            const response = await axios.get(`${API_BASE}/autocomplete`, { params: { query, limit } });
+            const response = await client.get(`${API_BASE}/autocomplete`, { params: { query, limit } });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to fetch autocomplete results."];
@@ -108,7 +167,7 @@ const apiService = {
 
     async searchItems(query, limit) {
         try {
-            const response = await axios.get(`${API_BASE}/search`, { params: { query, limit } });
+            const response = await client.get(`${API_BASE}/search`, { params: { query, limit } });
             return [true, response.data];
         } catch (error) {
             return [false, error.response?.data?.message || "Failed to search items."];
@@ -0,0 +1,31 @@
+// Prices come from the server as { minor_units, currency }.
+const ZERO_DECIMAL_CURRENCIES = ["BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "VND", "VUV", "XAF", "XOF", "XPF"];
+const THREE_DECIMAL_CURRENCIES = ["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+function currencyExponent(currency) {
+    if (ZERO_DECIMAL_CURRENCIES.includes(currency)) return 0;
+    if (THREE_DECIMAL_CURRENCIES.includes(currency)) return 3;
+    return 2;
+}
+
+export function toAmount(money) {
+    if (money === null || money === undefined) return null;
+    if (typeof money === "number") return money;
+    return money.minor_units / 10 ** currencyExponent(money.currency);
+}
+
+// Prices are sent to the server as exact decimal strings.
+export function toDecimalString(amount, currency) {
+    return Number(amount).toFixed(currencyExponent(currency));
+}
+
+export function formatMoney(money) {
+    if (money === null || money === undefined) return "";
+    if (typeof money === "number") return `$ ${money.toFixed(2)}`;
+    return formatAmount(toAmount(money), money.currency);
+}
+
+// Formats an amount in major units, such as a price from a bid event.
+export function formatAmount(amount, currency) {
+    return `${Number(amount).toFixed(currencyExponent(currency))} ${currency}`;
+}
@@ -8,6 +8,7 @@ import requests
 import pymongo
 import time
 import io
+from datetime import datetime
 import os
 from dotenv import load_dotenv
 
@@ -105,17 +106,19 @@ def process_item():
 
         rounded_rating = round(rating , 1)
 
-        # Update database with rating and set status as ACTIVE
+        # Update database with rating, items with a future start stay SCHEDULED
+        auction_start = item.get("auction_start")
+        status = "SCHEDULED" if auction_start and auction_start > datetime.utcnow() else "ACTIVE"
         items_collection.update_one(
             {"_id": item_id},
-            {"$set": {"rating": rounded_rating, "status": "ACTIVE"}}
+            {"$set": {"rating": rounded_rating, "status": status}}
         )
 
         # Delete cached item from redis
         redis_client.delete(f"item_details:{item_id}")
 
         print(
-            f"Updated item {item_id} with rating {avg_rating} and status ACTIVE")
+            f"Updated item {item_id} with rating {avg_rating} and status {status}")
 
 
 if __name__ == "__main__":
@@ -0,0 +1,201 @@
+use actix_web::{
+    body::{BoxBody, MessageBody},
+    cookie::{time, Cookie, SameSite},
+    dev::{Payload, ServiceRequest, ServiceResponse},
+    error::InternalError,
+    http::header,
+    middleware::Next,
+    web, Error, FromRequest, HttpMessage, HttpRequest, HttpResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::future::{ready, Ready};
+
+use chrono::Utc;
+use uuid::Uuid;
+
+use crate::{
+    mongo::{MongoClient, Role},
+    redis::{RedisClient, RedisClientError},
+};
+
+pub const SESSION_COOKIE: &str = "session";
+pub const SESSION_PREFIX: &str = "session";
+
+/// A logged in session, stored in Redis under the hash of its token.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Session {
+    pub user_id: String,
+    pub created_at: String,
+}
+
+/// The user a request is authenticated as. Handlers take it as an extractor,
+/// which rejects the request with 401 when no valid session was presented.
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub user_id: String,
+}
+
+impl Principal {
+    /// Returns the principal's user id, after checking that an identity the
+    /// client also sent in the request body, if any, refers to the same user.
+    pub fn resolve(&self, claimed: Option<&str>) -> Result<String, Error> {
+        match claimed.map(str::trim) {
+            Some(claimed)
+                if !claimed.is_empty() && !claimed.eq_ignore_ascii_case(&self.user_id) =>
+            {
+                Err(InternalError::from_response(
+                    "identity mismatch",
+                    HttpResponse::Forbidden().json(json!({
+                        "status": "error",
+                        "message": "Request identity does not match the authenticated user",
+                    })),
+                )
+                .into())
+            }
+            _ => Ok(self.user_id.clone()),
+        }
+    }
+}
+
+impl FromRequest for Principal {
+    type Error = Error;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        ready(req.extensions().get::<Principal>().cloned().ok_or_else(|| {
+            InternalError::from_response(
+                "missing principal",
+                HttpResponse::Unauthorized().json(json!({
+                    "status": "error",
+                    "message": "Authentication required",
+                })),
+            )
+            .into()
+        }))
+    }
+}
+
+/// Session tokens are never stored as-is, so a Redis dump does not leak usable tokens.
+pub fn session_key(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Starts a session for `user_id` and returns its token. Only the hash of the
+/// token is stored, the token itself is handed to the client once.
+pub async fn create_session(
+    redis_client: &RedisClient,
+    user_id: &str,
+    ttl_seconds: u64,
+) -> Result<String, RedisClientError> {
+    let token = format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple());
+    let session = Session {
+        user_id: user_id.to_string(),
+        created_at: Utc::now().to_rfc3339(),
+    };
+    redis_client
+        .set_value(
+            SESSION_PREFIX,
+            &session_key(&token),
+            &session,
+            Some(ttl_seconds),
+        )
+        .await?;
+    Ok(token)
+}
+
+pub fn session_cookie(token: &str, ttl_seconds: u64) -> Cookie<'static> {
+    Cookie::build(SESSION_COOKIE, token.to_string())
+        .path("/")
+        .http_only(true)
+        .same_site(SameSite::Lax)
+        .max_age(time::Duration::seconds(ttl_seconds as i64))
+        .finish()
+}
+
+fn session_token(req: &ServiceRequest) -> Option<String> {
+    let bearer = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token.trim().to_string());
+
+    bearer
+        .or_else(|| {
+            req.cookie(SESSION_COOKIE)
+                .map(|cookie| cookie.value().to_string())
+        })
+        .filter(|token| !token.is_empty())
+}
+
+/// Resolves the session token sent as a bearer token or session cookie and
+/// attaches the matching `Principal` to the request. Requests without a valid
+/// session pass through unauthenticated; handlers decide whether they need one.
+pub async fn authentication_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<impl MessageBody>, Error> {
+    if let (Some(token), Some(redis_client)) = (
+        session_token(&req),
+        req.app_data::<web::Data<RedisClient>>().cloned(),
+    ) {
+        match redis_client
+            .get_value::<Session>(SESSION_PREFIX, &session_key(&token))
+            .await
+        {
+            Ok(Some(session)) => {
+                req.extensions_mut().insert(Principal {
+                    user_id: session.user_id,
+                });
+            }
+            Ok(None) => {}
+            Err(err) => eprintln!("Failed to look up session: {:?}", err),
+        }
+    }
+
+    next.call(req).await
+}
+
+/// Guards the admin scope: only authenticated users holding the admin role
+/// get through. Relies on `authentication_middleware` having run first.
+pub async fn require_admin_middleware(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    let principal = req.extensions().get::<Principal>().cloned();
+    let principal = match principal {
+        Some(principal) => principal,
+        None => {
+            return Ok(req.into_response(HttpResponse::Unauthorized().json(json!({
+                "status": "error",
+                "message": "Authentication required",
+            }))))
+        }
+    };
+
+    let is_admin = match req.app_data::<web::Data<MongoClient>>() {
+        Some(mongo_client) => mongo_client.has_role(&principal.user_id, Role::ADMIN).await,
+        None => Ok(false),
+    };
+    match is_admin {
+        Ok(true) => next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body),
+        Ok(false) => Ok(req.into_response(HttpResponse::Forbidden().json(json!({
+            "status": "error",
+            "message": "Admin role required",
+        })))),
+        Err(err) => {
+            eprintln!("Failed to check roles for {}: {:?}", principal.user_id, err);
+            Ok(
+                req.into_response(HttpResponse::InternalServerError().json(json!({
+                    "status": "error",
+                    "message": "Failed to check user roles",
+                }))),
+            )
+        }
+    }
+}
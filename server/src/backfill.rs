@@ -0,0 +1,168 @@
+use futures::TryStreamExt;
+use mongodb::bson::doc;
+use serde::Deserialize;
+use std::collections::HashSet;
+
+use crate::{
+    mongo::{Item, MongoClient, Operation},
+    redis::RedisClient,
+    types::CurrentBid,
+};
+
+// Amount fields per collection that used to be stored as dollar floats.
+const LEGACY_PRICE_FIELDS: [(&str, &[&str]); 4] = [
+    (
+        "items",
+        &[
+            "base_price",
+            "reserve_price",
+            "buy_now_price",
+            "clearing_price",
+            "dutch_schedule.start_price",
+            "dutch_schedule.floor_price",
+            "dutch_schedule.decrement",
+        ],
+    ),
+    ("bids", &["bid_price"]),
+    ("proxy_bids", &["max_bid", "increment"]),
+    ("lot_bids", &["bid_price"]),
+];
+
+#[derive(Deserialize)]
+struct BlockchainUserItemsResponse {
+    status: String,
+    items: Option<Vec<String>>,
+}
+
+async fn fetch_user_items(blockchain_api_base_uri: &str, user_id: &str) -> Option<Vec<String>> {
+    let response = reqwest::Client::new()
+        .get(format!("{}/userItems/{}", blockchain_api_base_uri, user_id))
+        .send()
+        .await
+        .ok()?
+        .json::<BlockchainUserItemsResponse>()
+        .await
+        .ok()?;
+
+    match response.status.as_str() {
+        "success" => response.items,
+        _ => None,
+    }
+}
+
+pub async fn backfill_item_sellers(mongo_client: &MongoClient, blockchain_api_base_uri: &str) {
+    let db = mongo_client.get_db();
+
+    let unowned_item_ids: Vec<String> = match db
+        .collection::<Item>("items")
+        .find(doc! {"seller": {"$exists": false}}, None)
+        .await
+    {
+        Ok(cursor) => cursor
+            .try_collect::<Vec<Item>>()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|item| item.id)
+            .collect(),
+        Err(err) => {
+            eprintln!("Failed to find items without a seller: {:?}", err);
+            return;
+        }
+    };
+
+    if unowned_item_ids.is_empty() {
+        return;
+    }
+
+    let owners: HashSet<String> = match db
+        .collection::<Operation>("operations")
+        .find(
+            doc! {"type": "ADD", "params.item_id": {"$in": &unowned_item_ids}},
+            None,
+        )
+        .await
+    {
+        Ok(cursor) => cursor
+            .try_collect::<Vec<Operation>>()
+            .await
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|operation| {
+                operation
+                    .params
+                    .get("owner")
+                    .and_then(|v| v.as_str().map(ToOwned::to_owned))
+            })
+            .collect(),
+        Err(err) => {
+            eprintln!("Failed to find item owners: {:?}", err);
+            return;
+        }
+    };
+
+    let mut backfilled = 0;
+    for owner in owners {
+        let item_ids = match fetch_user_items(blockchain_api_base_uri, &owner).await {
+            Some(item_ids) => item_ids,
+            None => {
+                eprintln!("Failed to fetch blockchain items for user {}", owner);
+                continue;
+            }
+        };
+
+        match mongo_client.backfill_item_sellers(&owner, &item_ids).await {
+            Ok(count) => backfilled += count,
+            Err(err) => eprintln!("Failed to backfill seller {}: {:?}", owner, err),
+        }
+    }
+
+    println!("Backfilled seller on {} items", backfilled);
+}
+
+/// Converts prices written before amounts carried a currency, in Mongo and in
+/// the cached current bids, so every reader sees the exact money format.
+pub async fn backfill_money_fields(mongo_client: &MongoClient, redis_client: &RedisClient) {
+    let mut converted = 0;
+    for (collection, fields) in LEGACY_PRICE_FIELDS {
+        match mongo_client.convert_legacy_prices(collection, fields).await {
+            Ok(count) => converted += count,
+            Err(err) => eprintln!("Failed to convert prices in {}: {:?}", collection, err),
+        }
+    }
+
+    let item_ids = match redis_client.scan_keys("current_bid").await {
+        Ok(item_ids) => item_ids,
+        Err(err) => {
+            eprintln!("Failed to list cached current bids: {:?}", err);
+            vec![]
+        }
+    };
+    for item_id in item_ids {
+        let cached = match redis_client
+            .get_value::<serde_json::Value>("current_bid", &item_id)
+            .await
+        {
+            Ok(Some(cached)) if cached["bid_price"].is_number() => cached,
+            _ => continue,
+        };
+        let converted_bid = match serde_json::from_value::<CurrentBid>(cached.clone())
+            .ok()
+            .and_then(|bid| serde_json::to_value(bid).ok())
+        {
+            Some(bid) => bid,
+            None => continue,
+        };
+        // Only swap if no newer bid landed in the meantime.
+        match redis_client
+            .compare_and_swap("current_bid", &item_id, Some(&cached), Some(&converted_bid))
+            .await
+        {
+            Ok(true) => converted += 1,
+            Ok(false) => {}
+            Err(err) => eprintln!("Failed to convert current bid of {}: {:?}", item_id, err),
+        }
+    }
+
+    println!("Converted {} legacy prices to exact money", converted);
+}
@@ -14,6 +14,25 @@ pub struct Config {
     pub aws_bucket: String,
     pub aws_region: String,
     pub elasticsearch_uri: String,
+    pub bid_increments: Option<String>,
+    pub soft_close_window_seconds: Option<i64>,
+    pub soft_close_extension_seconds: Option<i64>,
+    pub buy_now_disable_fraction: Option<f64>,
+    pub bid_rate_limit_window_seconds: Option<u64>,
+    pub bid_rate_limit_per_bidder: Option<u64>,
+    pub bid_rate_limit_per_item: Option<u64>,
+    pub signed_bid_threshold: Option<f64>,
+    pub signed_bid_max_validity_seconds: Option<i64>,
+    pub idempotency_key_ttl_seconds: Option<u64>,
+    pub notification_sink: Option<String>,
+    pub notification_file: Option<String>,
+    pub ending_soon_window_seconds: Option<i64>,
+    pub siwe_domain: String,
+    pub siwe_chain_id: u64,
+    pub siwe_nonce_ttl_seconds: Option<u64>,
+    pub session_ttl_seconds: Option<u64>,
+    pub receipt_signing_key: String,
+    pub admin_users: Option<String>,
     pub app: Application,
 }
 
@@ -1,7 +1,7 @@
 use elasticsearch::{
     http::transport::Transport,
     indices::{IndicesCreateParts, IndicesPutMappingParts},
-    Elasticsearch, Error, IndexParts, SearchParts,
+    DeleteParts, Elasticsearch, Error, IndexParts, SearchParts,
 };
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
@@ -9,6 +9,79 @@ use serde_json::{json, Value};
 pub struct ElasticSearchClient {
     client: Elasticsearch,
     index_name: String,
+    saved_search_index_name: String,
+}
+
+fn index_settings() -> Value {
+    json!({
+        "number_of_shards": 1,
+        "number_of_replicas": 0,
+        "analysis": {
+            "analyzer": {
+                "autocomplete": {
+                    "tokenizer": "standard",
+                    "filter": ["lowercase", "edge_ngram"]
+                },
+                "autocomplete_search": {
+                    "tokenizer": "standard",
+                    "filter": ["lowercase"]
+                }
+            },
+            "filter": {
+                "edge_ngram": {
+                    "type": "edge_ngram",
+                    "min_gram": 1,
+                    "max_gram": 20
+                }
+            }
+        }
+    })
+}
+
+fn item_properties() -> Value {
+    json!({
+        "item_name": {
+            "type": "text",
+            "analyzer": "autocomplete",
+            "search_analyzer": "autocomplete_search",
+            "fields": {
+                "keyword": {
+                    "type": "keyword"
+                }
+            }
+        },
+        "category": {
+            "type": "text",
+            "analyzer": "autocomplete",
+            "search_analyzer": "autocomplete_search",
+            "fields": {
+                "keyword": {
+                    "type": "keyword"
+                }
+            }
+        },
+        "item_id": {
+            "type": "keyword"
+        }
+    })
+}
+
+// Shared by live searches and saved searches so a saved search matches new
+// items exactly like running the same search would.
+fn search_query(query: &str) -> Value {
+    json!({
+        "multi_match": {
+            "query": query,
+            "fields": [
+                "item_name^2",
+                "item_name.keyword^3",
+                "category",
+                "category.keyword^2"
+            ],
+            "type": "best_fields",
+            "fuzziness": "AUTO"
+        }
+    })
 }
 
 impl ElasticSearchClient {
@@ -19,9 +92,13 @@ impl ElasticSearchClient {
         let elastic_client = ElasticSearchClient {
             client,
             index_name: index_name.to_string(),
+            saved_search_index_name: format!("{}_saved_searches", index_name),
         };
 
         elastic_client.create_index_if_not_exists().await?;
+        elastic_client
+            .create_saved_search_index_if_not_exists()
+            .await?;
 
         Ok(elastic_client)
     }
@@ -39,62 +116,41 @@ impl ElasticSearchClient {
         if response.status_code() == 404 {
             indices
                 .create(IndicesCreateParts::Index(&self.index_name))
-                .body(json!({
-                    "settings": {
-                        "number_of_shards": 1,
-                        "number_of_replicas": 0,
-                        "analysis": {
-                            "analyzer": {
-                                "autocomplete": {
-                                    "tokenizer": "standard",
-                                    "filter": ["lowercase", "edge_ngram"]
-                                },
-                                "autocomplete_search": {
-                                    "tokenizer": "standard",
-                                    "filter": ["lowercase"]
-                                }
-                            },
-                            "filter": {
-                                "edge_ngram": {
-                                    "type": "edge_ngram",
-                                    "min_gram": 1,
-                                    "max_gram": 20
-                                }
-                            }
-                        }
-                    }
-                }))
+                .body(json!({ "settings": index_settings() }))
                 .send()
                 .await?;
 
             indices
                 .put_mapping(IndicesPutMappingParts::Index(&[&self.index_name]))
+                .body(json!({ "properties": item_properties() }))
+                .send()
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Saved searches are stored as percolator queries next to copies of the
+    /// item fields, so new items can be matched against all of them at once.
+    async fn create_saved_search_index_if_not_exists(&self) -> Result<(), Error> {
+        let indices = self.client.indices();
+
+        let response = indices
+            .exists(elasticsearch::indices::IndicesExistsParts::Index(&[
+                &self.saved_search_index_name
+            ]))
+            .send()
+            .await?;
+
+        if response.status_code() == 404 {
+            let mut properties = item_properties();
+            properties["query"] = json!({ "type": "percolator" });
+
+            indices
+                .create(IndicesCreateParts::Index(&self.saved_search_index_name))
                 .body(json!({
-                    "properties": {
-                        "item_name": {
-                            "type": "text",
-                            "analyzer": "autocomplete",
-                            "search_analyzer": "autocomplete_search",
-                            "fields": {
-                                "keyword": {
-                                    "type": "keyword"
-                                }
-                            }
-                        },
-                        "category": {
-                            "type": "text",
-                            "analyzer": "autocomplete",
-                            "search_analyzer": "autocomplete_search",
-                            "fields": {
-                                "keyword": {
-                                    "type": "keyword"
-                                }
-                            }
-                        },
-                        "item_id": {
-                            "type": "keyword"
-                        }
-                    }
+                    "settings": index_settings(),
+                    "mappings": { "properties": properties }
                 }))
                 .send()
                 .await?;
@@ -133,19 +189,7 @@ impl ElasticSearchClient {
             .search(SearchParts::Index(&[&self.index_name]))
             .body(json!({
                 "size": limit,
-                "query": {
-                    "multi_match": {
-                        "query": query,
-                        "fields": [
-                            "item_name^2",
-                            "item_name.keyword^3",
-                            "category",
-                            "category.keyword^2"
-                        ],
-                        "type": "best_fields",
-                        "fuzziness": "AUTO"
-                    }
-                },
+                "query": search_query(query),
                 "highlight": {
                     "fields": {
                         "item_name": {},
@@ -309,6 +353,70 @@ impl ElasticSearchClient {
         Ok(results)
     }
 
+    pub async fn save_search(&self, search_id: &str, query: &str) -> Result<(), Error> {
+        self.client
+            .index(IndexParts::IndexId(
+                &self.saved_search_index_name,
+                search_id,
+            ))
+            .body(json!({ "query": search_query(query) }))
+            .refresh(elasticsearch::params::Refresh::True)
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_saved_search(&self, search_id: &str) -> Result<(), Error> {
+        self.client
+            .delete(DeleteParts::IndexId(
+                &self.saved_search_index_name,
+                search_id,
+            ))
+            .send()
+            .await?;
+
+        Ok(())
+    }
+
+    /// Ids of the saved searches whose query matches an item with this name and category.
+    pub async fn match_saved_searches(
+        &self,
+        item_name: &str,
+        category: &str,
+        limit: usize,
+    ) -> Result<Vec<String>, Error> {
+        let response = self
+            .client
+            .search(SearchParts::Index(&[&self.saved_search_index_name]))
+            .body(json!({
+                "size": limit,
+                "query": {
+                    "percolate": {
+                        "field": "query",
+                        "document": {
+                            "item_name": item_name,
+                            "category": category,
+                        }
+                    }
+                },
+                "_source": false
+            }))
+            .send()
+            .await?;
+
+        let response_body = response.json::<Value>().await?;
+
+        Ok(response_body["hits"]["hits"]
+            .as_array()
+            .map(|hits| {
+                hits.iter()
+                    .filter_map(|hit| hit["_id"].as_str().map(ToOwned::to_owned))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     pub async fn remove_item(&self, item_id: &str) -> Result<(), Error> {
         self.client
             .delete_by_query(elasticsearch::DeleteByQueryParts::Index(
@@ -0,0 +1,89 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, DateTime};
+use chrono::Utc;
+use mongodb::options::UpdateOptions;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    mongo::{is_duplicate_key_error, Item, MongoClient, WatchlistEntry},
+};
+
+#[derive(Deserialize)]
+struct WatchlistRequest {
+    user_id: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WatchlistResponse {
+    status: String,
+    message: String,
+}
+
+#[post("/api/v1/watchlist/{item_id}")]
+pub async fn add_to_watchlist_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    req_body: Option<web::Json<WatchlistRequest>>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let requested_user = req_body.and_then(|body| body.into_inner().user_id);
+    let user_id = match principal.resolve(requested_user.as_deref()) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+
+    let db = mongo_client.get_db();
+    match db
+        .collection::<Item>("items")
+        .find_one(doc! {"_id": &item_id}, None)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => {
+            return HttpResponse::NotFound().json(WatchlistResponse {
+                status: "error".to_string(),
+                message: "Item not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(WatchlistResponse {
+                status: "error".to_string(),
+                message: "Failed to fetch item".to_string(),
+            });
+        }
+    }
+
+    let update_options = UpdateOptions::builder().upsert(true).build();
+    match db
+        .collection::<WatchlistEntry>("watchlists")
+        .update_one(
+            doc! {"user_id": &user_id, "item_id": &item_id},
+            doc! {"$setOnInsert": {"created_at": DateTime::from_chrono(Utc::now())}},
+            update_options,
+        )
+        .await
+    {
+        Ok(result) if result.upserted_id.is_some() => {
+            HttpResponse::Created().json(WatchlistResponse {
+                status: "success".to_string(),
+                message: "Item added to watchlist".to_string(),
+            })
+        }
+        Ok(_) => HttpResponse::Ok().json(WatchlistResponse {
+            status: "success".to_string(),
+            message: "Item is already in watchlist".to_string(),
+        }),
+        // A concurrent add of the same item loses the upsert race on the
+        // unique (user_id, item_id) index.
+        Err(err) if is_duplicate_key_error(&err) => HttpResponse::Ok().json(WatchlistResponse {
+            status: "success".to_string(),
+            message: "Item is already in watchlist".to_string(),
+        }),
+        Err(_) => HttpResponse::InternalServerError().json(WatchlistResponse {
+            status: "error".to_string(),
+            message: "Failed to update watchlist".to_string(),
+        }),
+    }
+}
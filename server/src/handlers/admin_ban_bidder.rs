@@ -0,0 +1,88 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, DateTime};
+use mongodb::options::UpdateOptions;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    mongo::{AdminAction, AuditOutcome, BiddingBan, MongoClient},
+};
+
+#[derive(Deserialize)]
+struct AdminActionRequest {
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AdminActionResponse {
+    status: String,
+    message: String,
+}
+
+#[post("/bans/{user_id}")]
+pub async fn admin_ban_bidder_handler(
+    principal: Principal,
+    user_id: web::Path<String>,
+    req_body: Option<web::Json<AdminActionRequest>>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let user_id = user_id.into_inner().to_lowercase();
+    let reason = req_body.and_then(|body| body.into_inner().reason);
+
+    let audit_id = match mongo_client
+        .record_admin_action(
+            &principal.user_id,
+            AdminAction::BAN_BIDDER,
+            Some(&user_id),
+            reason.as_deref(),
+        )
+        .await
+    {
+        Ok(audit_id) => audit_id,
+        Err(err) => {
+            eprintln!("Failed to record admin action: {:?}", err);
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to record admin action".to_string(),
+            });
+        }
+    };
+
+    let update_options = UpdateOptions::builder().upsert(true).build();
+    if mongo_client
+        .get_db()
+        .collection::<BiddingBan>("bidding_bans")
+        .update_one(
+            doc! {"_id": &user_id},
+            doc! {"$set": {
+                "reason": &reason,
+                "banned_by": &principal.user_id,
+                "created_at": DateTime::now(),
+            }},
+            update_options,
+        )
+        .await
+        .is_err()
+    {
+        mongo_client
+            .finish_admin_action(
+                audit_id,
+                AuditOutcome::FAILED,
+                Some("Failed to store the ban"),
+            )
+            .await;
+        return HttpResponse::InternalServerError().json(AdminActionResponse {
+            status: "error".to_string(),
+            message: "Failed to ban user".to_string(),
+        });
+    }
+
+    mongo_client
+        .finish_admin_action(audit_id, AuditOutcome::SUCCEEDED, None)
+        .await;
+
+    HttpResponse::Ok().json(AdminActionResponse {
+        status: "success".to_string(),
+        message: "User banned from bidding".to_string(),
+    })
+}
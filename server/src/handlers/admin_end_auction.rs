@@ -0,0 +1,158 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, DateTime};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{AdminAction, AuditOutcome, Item, ItemStatus, MongoClient},
+    redis::RedisClient,
+    types::TransferSchedulerURI,
+};
+
+#[derive(Deserialize)]
+struct AdminActionRequest {
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AdminActionResponse {
+    status: String,
+    message: String,
+}
+
+/// Ends an open auction now. The close itself runs through the transfer
+/// scheduler, exactly as it would at the original end time.
+#[post("/items/{item_id}/end")]
+pub async fn admin_end_auction_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    req_body: Option<web::Json<AdminActionRequest>>,
+    mongo_client: web::Data<MongoClient>,
+    redis_client: web::Data<RedisClient>,
+    transfer_scheduler_uri: web::Data<TransferSchedulerURI>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let reason = req_body.and_then(|body| body.into_inner().reason);
+
+    let audit_id = match mongo_client
+        .record_admin_action(
+            &principal.user_id,
+            AdminAction::FORCE_END_AUCTION,
+            Some(&item_id),
+            reason.as_deref(),
+        )
+        .await
+    {
+        Ok(audit_id) => audit_id,
+        Err(err) => {
+            eprintln!("Failed to record admin action: {:?}", err);
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to record admin action".to_string(),
+            });
+        }
+    };
+
+    let items_collection = mongo_client.get_db().collection::<Item>("items");
+    let ended_at = DateTime::now();
+    let ended = items_collection
+        .find_one_and_update(
+            doc! {
+                "_id": &item_id,
+                "status": {"$in": [ItemStatus::ACTIVE.to_string(), ItemStatus::SCHEDULED.to_string()]},
+            },
+            doc! {"$set": {"auction_end": ended_at}},
+            None,
+        )
+        .await;
+
+    let original_auction_end = match ended {
+        Ok(Some(item)) => item.auction_end,
+        Ok(None) => {
+            let message = "Item not found or its auction is not open";
+            mongo_client
+                .finish_admin_action(audit_id, AuditOutcome::FAILED, Some(message))
+                .await;
+            return HttpResponse::BadRequest().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: message.to_string(),
+            });
+        }
+        Err(_) => {
+            mongo_client
+                .finish_admin_action(
+                    audit_id,
+                    AuditOutcome::FAILED,
+                    Some("Failed to move the auction end"),
+                )
+                .await;
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to end auction".to_string(),
+            });
+        }
+    };
+
+    let rescheduled = reqwest::Client::new()
+        .post(format!(
+            "{}/reschedule",
+            transfer_scheduler_uri.uri.trim_end_matches('/')
+        ))
+        .json(&json!({
+            "item_id": &item_id,
+            "delay": 0,
+        }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status());
+    if let Err(e) = rescheduled {
+        eprintln!("Failed to reschedule auction close: {:?}", e);
+        // Without a scheduled close the auction would sit past its end but
+        // never close, so it keeps its original end time instead.
+        if let Err(err) = items_collection
+            .update_one(
+                doc! {"_id": &item_id, "auction_end": ended_at},
+                doc! {"$set": {"auction_end": original_auction_end}},
+                None,
+            )
+            .await
+        {
+            eprintln!(
+                "Failed to restore auction end for item id: {}. Error : {:?}",
+                item_id, err
+            );
+        }
+        mongo_client
+            .finish_admin_action(
+                audit_id,
+                AuditOutcome::FAILED,
+                Some("The auction close could not be scheduled"),
+            )
+            .await;
+        return HttpResponse::BadGateway().json(AdminActionResponse {
+            status: "error".to_string(),
+            message: "The auction close could not be scheduled, the auction was left open"
+                .to_string(),
+        });
+    }
+
+    if let Err(err) = redis_client
+        .delete_key(&format!("item_details:{}", item_id))
+        .await
+    {
+        eprintln!(
+            "Failed to delete item details for item id: {}. Error : {:?}",
+            item_id, err
+        );
+    }
+
+    mongo_client
+        .finish_admin_action(audit_id, AuditOutcome::SUCCEEDED, None)
+        .await;
+
+    HttpResponse::Ok().json(AdminActionResponse {
+        status: "success".to_string(),
+        message: "Auction ended, it will be closed shortly".to_string(),
+    })
+}
@@ -0,0 +1,123 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    mongo::{AdminAction, AuditOutcome, MongoClient, Operation, OperationStatus},
+};
+
+const DEFAULT_LIMIT: i64 = 50;
+const MAX_LIMIT: i64 = 200;
+
+#[derive(Deserialize)]
+struct FailedOperationsQuery {
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct FailedOperation {
+    operation_id: String,
+    operation: String,
+    params: serde_json::Value,
+    error: Option<String>,
+    transaction_hash: Option<String>,
+    created_at: String,
+    updated_at: String,
+}
+
+#[get("/operations/failed")]
+pub async fn admin_get_failed_operations_handler(
+    principal: Principal,
+    query: web::Query<FailedOperationsQuery>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let audit_id = match mongo_client
+        .record_admin_action(
+            &principal.user_id,
+            AdminAction::VIEW_FAILED_OPERATIONS,
+            None,
+            None,
+        )
+        .await
+    {
+        Ok(audit_id) => audit_id,
+        Err(err) => {
+            eprintln!("Failed to record admin action: {:?}", err);
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "status": "error",
+                "message": "Failed to record admin action",
+            }));
+        }
+    };
+
+    let limit = query.limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT);
+    let find_options = FindOptions::builder()
+        .sort(doc! {"updated_at": -1})
+        .limit(limit)
+        .build();
+
+    let operations: Vec<Operation> = match mongo_client
+        .get_db()
+        .collection::<Operation>("operations")
+        .find(
+            doc! {"status": OperationStatus::FAILED.to_string()},
+            find_options,
+        )
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(operations) => operations,
+            Err(_) => {
+                mongo_client
+                    .finish_admin_action(
+                        audit_id,
+                        AuditOutcome::FAILED,
+                        Some("Failed to fetch operations"),
+                    )
+                    .await;
+                return HttpResponse::InternalServerError().json(serde_json::json!({
+                    "status": "error",
+                    "message": "Failed to fetch operations",
+                }));
+            }
+        },
+        Err(_) => {
+            mongo_client
+                .finish_admin_action(
+                    audit_id,
+                    AuditOutcome::FAILED,
+                    Some("Failed to fetch operations"),
+                )
+                .await;
+            return HttpResponse::InternalServerError().json(serde_json::json!({
+                "status": "error",
+                "message": "Failed to fetch operations",
+            }));
+        }
+    };
+
+    mongo_client
+        .finish_admin_action(audit_id, AuditOutcome::SUCCEEDED, None)
+        .await;
+
+    let operations: Vec<FailedOperation> = operations
+        .into_iter()
+        .map(|operation| FailedOperation {
+            operation_id: operation.operation_id,
+            operation: operation.r#type.to_string(),
+            params: operation.params,
+            error: operation.error,
+            transaction_hash: operation.transaction_hash,
+            created_at: operation.created_at.to_chrono().to_rfc3339(),
+            updated_at: operation.updated_at.to_chrono().to_rfc3339(),
+        })
+        .collect();
+
+    HttpResponse::Ok().json(serde_json::json!({
+        "status": "success",
+        "operations": operations,
+    }))
+}
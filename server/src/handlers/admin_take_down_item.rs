@@ -0,0 +1,133 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::doc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::auction_close::remove_from_listings;
+use crate::{
+    auth::Principal,
+    elasticsearch::ElasticSearchClient,
+    mongo::{AdminAction, AuditOutcome, Item, ItemStatus, MongoClient},
+    redis::RedisClient,
+    types::TransferSchedulerURI,
+};
+
+#[derive(Deserialize)]
+struct AdminActionRequest {
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AdminActionResponse {
+    status: String,
+    message: String,
+}
+
+/// Takes a listing out of the marketplace without deleting it, so its bids
+/// stay available for review. Items already sold cannot be taken down.
+#[post("/items/{item_id}/take-down")]
+pub async fn admin_take_down_item_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    req_body: Option<web::Json<AdminActionRequest>>,
+    mongo_client: web::Data<MongoClient>,
+    redis_client: web::Data<RedisClient>,
+    elasticsearch_client: web::Data<ElasticSearchClient>,
+    transfer_scheduler_uri: web::Data<TransferSchedulerURI>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let reason = req_body.and_then(|body| body.into_inner().reason);
+
+    let audit_id = match mongo_client
+        .record_admin_action(
+            &principal.user_id,
+            AdminAction::TAKE_DOWN_ITEM,
+            Some(&item_id),
+            reason.as_deref(),
+        )
+        .await
+    {
+        Ok(audit_id) => audit_id,
+        Err(err) => {
+            eprintln!("Failed to record admin action: {:?}", err);
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to record admin action".to_string(),
+            });
+        }
+    };
+
+    let removed = mongo_client
+        .get_db()
+        .collection::<Item>("items")
+        .update_one(
+            doc! {
+                "_id": &item_id,
+                "status": {"$in": [
+                    ItemStatus::PENDING.to_string(),
+                    ItemStatus::SCHEDULED.to_string(),
+                    ItemStatus::ACTIVE.to_string(),
+                    ItemStatus::UNSOLD.to_string(),
+                ]},
+            },
+            doc! {"$set": {"status": ItemStatus::REMOVED.to_string()}},
+            None,
+        )
+        .await;
+
+    match removed {
+        Ok(result) if result.matched_count == 1 => {}
+        Ok(_) => {
+            let message = "Item not found, already removed or already sold";
+            mongo_client
+                .finish_admin_action(audit_id, AuditOutcome::FAILED, Some(message))
+                .await;
+            return HttpResponse::BadRequest().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: message.to_string(),
+            });
+        }
+        Err(_) => {
+            mongo_client
+                .finish_admin_action(
+                    audit_id,
+                    AuditOutcome::FAILED,
+                    Some("Failed to update the item status"),
+                )
+                .await;
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to take down item".to_string(),
+            });
+        }
+    }
+
+    let cancelled = reqwest::Client::new()
+        .post(format!(
+            "{}/cancel",
+            transfer_scheduler_uri.uri.trim_end_matches('/')
+        ))
+        .json(&json!({ "item_id": &item_id }))
+        .send()
+        .await;
+    if let Err(e) = &cancelled {
+        eprintln!("Failed to cancel auction close: {:?}", e);
+    }
+
+    remove_from_listings(&redis_client, &elasticsearch_client, &item_id).await;
+
+    mongo_client
+        .finish_admin_action(
+            audit_id,
+            AuditOutcome::SUCCEEDED,
+            cancelled
+                .is_err()
+                .then_some("The scheduled auction close could not be cancelled"),
+        )
+        .await;
+
+    HttpResponse::Ok().json(AdminActionResponse {
+        status: "success".to_string(),
+        message: "Item taken down".to_string(),
+    })
+}
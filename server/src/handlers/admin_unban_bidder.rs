@@ -0,0 +1,89 @@
+use actix_web::{delete, web, HttpResponse, Responder};
+use bson::doc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    mongo::{AdminAction, AuditOutcome, BiddingBan, MongoClient},
+};
+
+#[derive(Deserialize)]
+struct AdminActionRequest {
+    reason: Option<String>,
+}
+
+#[derive(Serialize)]
+struct AdminActionResponse {
+    status: String,
+    message: String,
+}
+
+#[delete("/bans/{user_id}")]
+pub async fn admin_unban_bidder_handler(
+    principal: Principal,
+    user_id: web::Path<String>,
+    req_body: Option<web::Json<AdminActionRequest>>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let user_id = user_id.into_inner().to_lowercase();
+    let reason = req_body.and_then(|body| body.into_inner().reason);
+
+    let audit_id = match mongo_client
+        .record_admin_action(
+            &principal.user_id,
+            AdminAction::UNBAN_BIDDER,
+            Some(&user_id),
+            reason.as_deref(),
+        )
+        .await
+    {
+        Ok(audit_id) => audit_id,
+        Err(err) => {
+            eprintln!("Failed to record admin action: {:?}", err);
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to record admin action".to_string(),
+            });
+        }
+    };
+
+    match mongo_client
+        .get_db()
+        .collection::<BiddingBan>("bidding_bans")
+        .delete_one(doc! {"_id": &user_id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 1 => {}
+        Ok(_) => {
+            mongo_client
+                .finish_admin_action(audit_id, AuditOutcome::FAILED, Some("User is not banned"))
+                .await;
+            return HttpResponse::NotFound().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "User is not banned".to_string(),
+            });
+        }
+        Err(_) => {
+            mongo_client
+                .finish_admin_action(
+                    audit_id,
+                    AuditOutcome::FAILED,
+                    Some("Failed to delete the ban"),
+                )
+                .await;
+            return HttpResponse::InternalServerError().json(AdminActionResponse {
+                status: "error".to_string(),
+                message: "Failed to lift ban".to_string(),
+            });
+        }
+    }
+
+    mongo_client
+        .finish_admin_action(audit_id, AuditOutcome::SUCCEEDED, None)
+        .await;
+
+    HttpResponse::Ok().json(AdminActionResponse {
+        status: "success".to_string(),
+        message: "User can bid again".to_string(),
+    })
+}
@@ -0,0 +1,344 @@
+use bson::{doc, oid::ObjectId, Bson};
+use serde::Serialize;
+use serde_json::json;
+
+use super::place_bid::{bid_event, count_bids, retract_bid};
+use crate::{
+    elasticsearch::ElasticSearchClient,
+    money::Currency,
+    mongo::{Bid, Item, ItemStatus, MongoClient},
+    notifications::queue_outbid_notification,
+    redis::RedisClient,
+    types::{BidEventKind, CurrentBid, TransferSchedulerURI},
+};
+
+#[derive(Debug)]
+pub(crate) enum AuctionCloseError {
+    Conflict,
+    NotActive,
+    CacheError,
+    DatabaseError,
+    TransferError,
+}
+
+#[derive(Serialize)]
+struct TransferringItemDetails {
+    item_id: String,
+    item_name: String,
+    price: String,
+    currency: Currency,
+    seller: String,
+}
+
+#[derive(Serialize)]
+struct TransferMessage {
+    item_id: String,
+    user_id: String,
+    price: String,
+    currency: Currency,
+    item_name: String,
+    seller: String,
+}
+
+pub(crate) async fn remove_from_listings(
+    redis_client: &RedisClient,
+    elasticsearch_client: &ElasticSearchClient,
+    item_id: &str,
+) {
+    let similar_items = match redis_client.get_similar_items(item_id).await {
+        Ok(Some(items)) => items,
+        Ok(None) => vec![],
+        Err(err) => {
+            eprintln!(
+                "Failed to fetch similar items for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            vec![]
+        }
+    };
+
+    for similar_item_id in similar_items {
+        if let Ok(Some(mut refs)) = redis_client.get_similar_items(&similar_item_id).await {
+            refs.retain(|id| id != item_id);
+
+            if refs.is_empty() {
+                let _ = redis_client
+                    .delete_key(&format!("similar_items:{}", similar_item_id))
+                    .await;
+            } else {
+                let _ = redis_client
+                    .set_similar_items(&similar_item_id, &refs)
+                    .await;
+            }
+        }
+    }
+
+    let _ = redis_client
+        .delete_key(&format!("similar_items:{}", item_id))
+        .await;
+    let _ = redis_client
+        .delete_key(&format!("item_details:{}", item_id))
+        .await;
+
+    if let Err(err) = elasticsearch_client.remove_item(item_id).await {
+        eprintln!("Failed to remove item from elastic search : {:?}", err);
+    }
+}
+
+/// Undoes a close that failed part way: the item goes back to its previous
+/// status and end time, and the winning bid is withdrawn again.
+async fn reopen_auction(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    item: &Item,
+    previous_bid: Option<&CurrentBid>,
+    winning_bid: &CurrentBid,
+    recorded_bid: Option<ObjectId>,
+) {
+    let item_id = item.id.as_deref().unwrap_or_default();
+    let db = mongo_client.get_db();
+
+    if let Some(bid_id) = recorded_bid {
+        if let Err(err) = db
+            .collection::<Bid>("bids")
+            .delete_one(doc! {"_id": bid_id}, None)
+            .await
+        {
+            eprintln!(
+                "Failed to remove winning bid for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+        }
+    }
+
+    if let Err(err) = db
+        .collection::<Item>("items")
+        .update_one(
+            doc! {"_id": item_id, "status": ItemStatus::TRANSFERRING.to_string()},
+            doc! {"$set": {
+                "status": Bson::String(item.status.to_string()),
+                "auction_end": item.auction_end,
+            }},
+            None,
+        )
+        .await
+    {
+        eprintln!(
+            "Failed to reopen auction for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+    }
+
+    let bid_count = count_bids(&db.collection::<Bid>("bids"), item_id).await;
+    if let Err(err) = retract_bid(
+        redis_client,
+        item,
+        winning_bid,
+        previous_bid,
+        bid_count,
+        &item.auction_end.to_chrono().to_rfc3339(),
+    )
+    .await
+    {
+        eprintln!(
+            "Failed to roll back current bid for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+    }
+}
+
+/// Closes an active auction immediately with `bid` as the winner and hands the
+/// item over to the transfer flow, the same way the worker does at auction end.
+/// Marking the item TRANSFERRING claims it, so a step failing after that puts
+/// the auction back the way it was.
+pub(crate) async fn close_auction_with_winner(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    elasticsearch_client: &ElasticSearchClient,
+    transfer_scheduler_uri: &TransferSchedulerURI,
+    item: &Item,
+    previous_bid: Option<&CurrentBid>,
+    mut bid: Bid,
+) -> Result<(), AuctionCloseError> {
+    let item_id = bid.item_id.clone();
+    let bid_id = *bid.id.get_or_insert_with(ObjectId::new);
+    let winning_bid = CurrentBid {
+        bid_price: bid.bid_price,
+        bidder: bid.bidder.clone(),
+    };
+
+    // The sale is announced on the item's event stream as part of the swap.
+    let db = mongo_client.get_db();
+    let bid_count = count_bids(&db.collection::<Bid>("bids"), &item_id).await;
+    match redis_client
+        .compare_and_swap_with_event(
+            "current_bid",
+            &item_id,
+            previous_bid,
+            Some(&winning_bid),
+            &item_id,
+            &bid_event(
+                BidEventKind::SOLD,
+                &item_id,
+                &winning_bid,
+                bid_count + 1,
+                bid.timestamp,
+                &bid.timestamp.to_chrono().to_rfc3339(),
+            ),
+        )
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(AuctionCloseError::Conflict),
+        Err(_) => return Err(AuctionCloseError::CacheError),
+    }
+
+    let closed = db
+        .collection::<Item>("items")
+        .update_one(
+            doc! {
+                "_id": &item_id,
+                "status": {"$in": [ItemStatus::ACTIVE.to_string(), ItemStatus::SCHEDULED.to_string()]},
+            },
+            doc! {"$set": {
+                "status": Bson::String(ItemStatus::TRANSFERRING.to_string()),
+                "auction_end": bid.timestamp,
+            }},
+            None,
+        )
+        .await;
+
+    match closed {
+        Ok(result) if result.modified_count == 1 => {}
+        _ => {
+            if let Err(err) = retract_bid(
+                redis_client,
+                item,
+                &winning_bid,
+                previous_bid,
+                bid_count,
+                &item.auction_end.to_chrono().to_rfc3339(),
+            )
+            .await
+            {
+                eprintln!(
+                    "Failed to roll back current bid for item_id: {}. Error: {:?}",
+                    item_id, err
+                );
+            }
+            return Err(AuctionCloseError::NotActive);
+        }
+    }
+
+    if let Err(err) = db.collection::<Bid>("bids").insert_one(bid, None).await {
+        eprintln!(
+            "Failed to record winning bid for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+        reopen_auction(
+            mongo_client,
+            redis_client,
+            item,
+            previous_bid,
+            &winning_bid,
+            None,
+        )
+        .await;
+        return Err(AuctionCloseError::DatabaseError);
+    }
+
+    let seller = match &item.seller {
+        Some(seller) => seller.clone(),
+        None => mongo_client
+            .find_item_seller(&item_id)
+            .await
+            .ok()
+            .flatten()
+            .unwrap_or_default(),
+    };
+    let price = winning_bid.bid_price.to_string();
+
+    if let Err(err) = redis_client
+        .add_item_to_user_list(
+            &winning_bid.bidder,
+            &TransferringItemDetails {
+                item_id: item_id.clone(),
+                item_name: item.title.clone(),
+                price: price.clone(),
+                currency: winning_bid.bid_price.currency,
+                seller: seller.clone(),
+            },
+        )
+        .await
+    {
+        eprintln!(
+            "Failed to update transferring items for buyer: {}. Error: {:?}",
+            winning_bid.bidder, err
+        );
+        reopen_auction(
+            mongo_client,
+            redis_client,
+            item,
+            previous_bid,
+            &winning_bid,
+            Some(bid_id),
+        )
+        .await;
+        return Err(AuctionCloseError::TransferError);
+    }
+
+    // Cancelled last, so that a reopened auction still closes on schedule.
+    if let Err(e) = reqwest::Client::new()
+        .post(format!(
+            "{}/cancel",
+            transfer_scheduler_uri.uri.trim_end_matches('/')
+        ))
+        .json(&json!({ "item_id": item_id }))
+        .send()
+        .await
+        .and_then(|response| response.error_for_status())
+    {
+        eprintln!("Failed to cancel auction close: {:?}", e);
+        if let Err(err) = redis_client
+            .remove_items_with_id_from_user_list(&winning_bid.bidder, &item_id)
+            .await
+        {
+            eprintln!(
+                "Failed to update transferring items for buyer: {}. Error: {:?}",
+                winning_bid.bidder, err
+            );
+        }
+        reopen_auction(
+            mongo_client,
+            redis_client,
+            item,
+            previous_bid,
+            &winning_bid,
+            Some(bid_id),
+        )
+        .await;
+        return Err(AuctionCloseError::TransferError);
+    }
+
+    queue_outbid_notification(redis_client, &item_id, previous_bid, &winning_bid).await;
+
+    let transfer_message = TransferMessage {
+        item_id: item_id.clone(),
+        user_id: winning_bid.bidder,
+        price,
+        currency: winning_bid.bid_price.currency,
+        item_name: item.title.clone(),
+        seller,
+    };
+    if redis_client
+        .publish(&transfer_message, "transfer")
+        .await
+        .is_err()
+    {
+        eprintln!("Failed to publish transfer update");
+    }
+
+    remove_from_listings(redis_client, elasticsearch_client, &item_id).await;
+
+    Ok(())
+}
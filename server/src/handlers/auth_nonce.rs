@@ -0,0 +1,51 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use chrono::Utc;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::{redis::RedisClient, types::AuthConfig};
+
+pub const SIWE_NONCE_PREFIX: &str = "siwe_nonce";
+
+#[derive(Serialize)]
+struct NonceResponse {
+    status: String,
+    nonce: String,
+    issued_at: String,
+    expires_in_seconds: u64,
+}
+
+/// Issues a single-use nonce for the client to embed in its Sign-In with
+/// Ethereum message.
+#[get("/api/v1/auth/nonce")]
+pub async fn auth_nonce_handler(
+    redis_client: web::Data<RedisClient>,
+    auth_config: web::Data<AuthConfig>,
+) -> impl Responder {
+    let nonce = Uuid::new_v4().simple().to_string();
+    let issued_at = Utc::now().to_rfc3339();
+
+    match redis_client
+        .set_value(
+            SIWE_NONCE_PREFIX,
+            &nonce,
+            &issued_at,
+            Some(auth_config.nonce_ttl_seconds),
+        )
+        .await
+    {
+        Ok(_) => HttpResponse::Ok().json(NonceResponse {
+            status: "success".to_string(),
+            nonce,
+            issued_at,
+            expires_in_seconds: auth_config.nonce_ttl_seconds,
+        }),
+        Err(err) => {
+            eprintln!("Failed to store sign in nonce: {:?}", err);
+            HttpResponse::InternalServerError().json(serde_json::json!({
+                "status": "error",
+                "message": "Failed to create nonce",
+            }))
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::{create_session, session_cookie},
+    handlers::auth_nonce::SIWE_NONCE_PREFIX,
+    redis::RedisClient,
+    siwe::{self, SiweError},
+    types::AuthConfig,
+};
+
+#[derive(Deserialize)]
+struct VerifyRequest {
+    message: String,
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct VerifyResponse {
+    status: String,
+    message: String,
+}
+
+#[derive(Serialize)]
+struct SessionResponse {
+    status: String,
+    user_id: String,
+    token: String,
+    expires_at: String,
+}
+
+fn unauthorized(message: &str) -> HttpResponse {
+    HttpResponse::Unauthorized().json(VerifyResponse {
+        status: "error".to_string(),
+        message: message.to_string(),
+    })
+}
+
+/// Verifies a signed Sign-In with Ethereum message and starts a session for
+/// the address that signed it. The signature is checked offline by recovering
+/// the signer's public key, so no chain connection is needed.
+#[post("/api/v1/auth/verify")]
+pub async fn auth_verify_handler(
+    req_body: web::Json<VerifyRequest>,
+    redis_client: web::Data<RedisClient>,
+    auth_config: web::Data<AuthConfig>,
+) -> impl Responder {
+    let message = match siwe::verify(&req_body.message, &req_body.signature) {
+        Ok(message) => message,
+        Err(SiweError::MalformedMessage(reason)) => {
+            return HttpResponse::BadRequest().json(VerifyResponse {
+                status: "error".to_string(),
+                message: format!("Invalid sign in message: {}", reason),
+            });
+        }
+        Err(SiweError::InvalidSignature) => return unauthorized("Invalid signature"),
+        Err(SiweError::AddressMismatch) => {
+            return unauthorized("Signature does not match the message address")
+        }
+    };
+
+    if message.domain != auth_config.siwe_domain {
+        return unauthorized("Sign in message is for a different domain");
+    }
+    if message.chain_id != auth_config.siwe_chain_id {
+        return unauthorized("Sign in message is for a different chain");
+    }
+    if message.version != "1" {
+        return unauthorized("Unsupported sign in message version");
+    }
+    if !message.is_valid_at(Utc::now()) {
+        return unauthorized("Sign in message has expired or is not valid yet");
+    }
+
+    // Taking the nonce deletes it, so a signed message can only be used once.
+    match redis_client
+        .take_value::<String>(SIWE_NONCE_PREFIX, &message.nonce)
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return unauthorized("Unknown or already used nonce"),
+        Err(err) => {
+            eprintln!("Failed to check sign in nonce: {:?}", err);
+            return HttpResponse::InternalServerError().json(VerifyResponse {
+                status: "error".to_string(),
+                message: "Failed to check nonce".to_string(),
+            });
+        }
+    }
+
+    // Addresses arrive checksummed or not, sessions always use the lowercase form.
+    let user_id = message.address.to_lowercase();
+    let ttl_seconds = auth_config.session_ttl_seconds;
+    match create_session(&redis_client, &user_id, ttl_seconds).await {
+        Ok(token) => HttpResponse::Ok()
+            .cookie(session_cookie(&token, ttl_seconds))
+            .json(SessionResponse {
+                status: "success".to_string(),
+                user_id,
+                token,
+                expires_at: (Utc::now() + chrono::Duration::seconds(ttl_seconds as i64))
+                    .to_rfc3339(),
+            }),
+        Err(err) => {
+            eprintln!("Failed to create session for {}: {:?}", user_id, err);
+            HttpResponse::InternalServerError().json(VerifyResponse {
+                status: "error".to_string(),
+                message: "Failed to create session".to_string(),
+            })
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, DateTime};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::auction_close::{close_auction_with_winner, AuctionCloseError};
+use super::place_bid::{
+    authorization_exceeded_response, exceeds_authorization, release_bid_nonce,
+    signature_required_response, verify_bid_authorization, BidAuthorization,
+};
+use crate::{
+    auth::Principal,
+    elasticsearch::ElasticSearchClient,
+    mongo::{Bid, Item, MongoClient},
+    redis::RedisClient,
+    types::{BiddingConfig, CurrentBid, TransferSchedulerURI},
+};
+
+#[derive(Deserialize)]
+struct BuyNowRequest {
+    buyer: Option<String>,
+    authorization: Option<BidAuthorization>,
+}
+
+#[derive(Serialize)]
+struct BuyNowResponse {
+    status: String,
+    message: String,
+}
+
+#[post("/api/v1/item/{item_id}/buy-now")]
+#[allow(clippy::too_many_arguments)]
+pub async fn buy_now_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    req_body: web::Json<BuyNowRequest>,
+    mongo_client: web::Data<MongoClient>,
+    redis_client: web::Data<RedisClient>,
+    elasticsearch_client: web::Data<ElasticSearchClient>,
+    transfer_scheduler_uri: web::Data<TransferSchedulerURI>,
+    bidding_config: web::Data<BiddingConfig>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let buyer = match principal.resolve(req_body.buyer.as_deref()) {
+        Ok(buyer) => buyer,
+        Err(err) => return err.error_response(),
+    };
+
+    match mongo_client.is_banned_from_bidding(&buyer).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return HttpResponse::Forbidden().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "You are banned from bidding".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Database error".to_string(),
+            });
+        }
+    }
+
+    let db = mongo_client.get_db();
+    let items_collection = db.collection::<Item>("items");
+
+    let item = match items_collection
+        .find_one(doc! {"_id": &item_id}, None)
+        .await
+    {
+        Ok(Some(item)) if item.is_open(Utc::now()) => item,
+        Ok(Some(_)) => {
+            return HttpResponse::BadRequest().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Item is not available for buy now".to_string(),
+            });
+        }
+        Ok(None) => {
+            return HttpResponse::NotFound().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Item not found".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Database error".to_string(),
+            });
+        }
+    };
+
+    let seller = match mongo_client.find_item_seller(&item_id).await {
+        Ok(seller) => seller.unwrap_or_default(),
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Database error".to_string(),
+            });
+        }
+    };
+    if seller.eq_ignore_ascii_case(&buyer) {
+        return HttpResponse::Forbidden().json(BuyNowResponse {
+            status: "error".to_string(),
+            message: "Sellers cannot buy their own items".to_string(),
+        });
+    }
+
+    let buy_now_price = match item.buy_now_price {
+        Some(price) if item.auction_end.to_chrono() > Utc::now() => price,
+        _ => {
+            return HttpResponse::BadRequest().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Buy now is not available for this item".to_string(),
+            });
+        }
+    };
+
+    let previous_bid: Option<CurrentBid> =
+        match redis_client.get_value("current_bid", &item_id).await {
+            Ok(bid) => bid,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(BuyNowResponse {
+                    status: "error".to_string(),
+                    message: "Failed to retrieve current bid".to_string(),
+                });
+            }
+        };
+
+    if !bidding_config.buy_now.is_available(
+        buy_now_price,
+        previous_bid.as_ref().map(|bid| bid.bid_price),
+    ) {
+        return HttpResponse::BadRequest().json(BuyNowResponse {
+            status: "error".to_string(),
+            message: "Buy now is no longer available for this item".to_string(),
+        });
+    }
+
+    // Buying wins the auction outright, so it needs a signature whenever a bid
+    // of the same amount would.
+    let signature = match &req_body.authorization {
+        Some(authorization) => match verify_bid_authorization(
+            &redis_client,
+            &bidding_config.signed_bids,
+            &item,
+            &buyer,
+            authorization,
+        )
+        .await
+        {
+            Ok(signature) => Some(signature),
+            Err(err) => return err.error_response(),
+        },
+        None if bidding_config.signed_bids.is_required(buy_now_price) => {
+            return signature_required_response();
+        }
+        None => None,
+    };
+    if exceeds_authorization(signature.as_ref(), buy_now_price) {
+        if let Some(signature) = &signature {
+            release_bid_nonce(&redis_client, signature).await;
+        }
+        return authorization_exceeded_response(signature.as_ref());
+    }
+
+    let bid = Bid {
+        id: None,
+        item_id: item_id.clone(),
+        bidder: buyer,
+        bid_price: buy_now_price,
+        timestamp: DateTime::from_chrono(Utc::now()),
+        proxy: false,
+        buy_now: true,
+        quantity: None,
+        signature: signature.clone(),
+    };
+
+    let result = close_auction_with_winner(
+        &mongo_client,
+        &redis_client,
+        &elasticsearch_client,
+        &transfer_scheduler_uri,
+        &item,
+        previous_bid.as_ref(),
+        bid,
+    )
+    .await;
+
+    // The authorization's nonce is only spent once the item is bought.
+    if let (Err(_), Some(signature)) = (&result, &signature) {
+        release_bid_nonce(&redis_client, signature).await;
+    }
+
+    match result {
+        Ok(()) => HttpResponse::Ok().json(BuyNowResponse {
+            status: "success".to_string(),
+            message: "Item bought, it will shortly be available for transfer".to_string(),
+        }),
+        Err(AuctionCloseError::Conflict) => HttpResponse::Conflict().json(BuyNowResponse {
+            status: "error".to_string(),
+            message: "A bid was placed concurrently, please retry".to_string(),
+        }),
+        Err(AuctionCloseError::NotActive) => HttpResponse::BadRequest().json(BuyNowResponse {
+            status: "error".to_string(),
+            message: "Item is not available for buy now".to_string(),
+        }),
+        Err(AuctionCloseError::CacheError) => {
+            HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Failed to store bid in cache".to_string(),
+            })
+        }
+        Err(AuctionCloseError::DatabaseError) => {
+            HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Failed to record bid".to_string(),
+            })
+        }
+        Err(AuctionCloseError::TransferError) => {
+            HttpResponse::InternalServerError().json(BuyNowResponse {
+                status: "error".to_string(),
+                message: "Failed to register item for transfer".to_string(),
+            })
+        }
+    }
+}
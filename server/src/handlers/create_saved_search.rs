@@ -0,0 +1,131 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, DateTime};
+use chrono::Utc;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    elasticsearch::ElasticSearchClient,
+    money::{Currency, PriceInput},
+    mongo::{AuctionType, MongoClient, SavedSearch, SavedSearchFilters},
+};
+
+#[derive(Deserialize, Default)]
+struct SavedSearchFiltersRequest {
+    category: Option<String>,
+    auction_type: Option<AuctionType>,
+    max_price: Option<PriceInput>,
+    currency: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct CreateSavedSearchRequest {
+    user_id: Option<String>,
+    query: String,
+    #[serde(default)]
+    filters: SavedSearchFiltersRequest,
+}
+
+#[post("/api/v1/saved-searches")]
+pub async fn create_saved_search_handler(
+    principal: Principal,
+    req_body: web::Json<CreateSavedSearchRequest>,
+    mongo_client: web::Data<MongoClient>,
+    elasticsearch_client: web::Data<ElasticSearchClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(req_body.user_id.as_deref()) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+    let query = req_body.query.trim();
+    if query.is_empty() {
+        return HttpResponse::BadRequest().json(json!({
+            "status": "error",
+            "message": "query is required"
+        }));
+    }
+
+    let currency = match req_body.filters.currency.as_deref() {
+        Some(code) => match Currency::parse(code) {
+            Some(currency) => currency,
+            None => {
+                return HttpResponse::BadRequest().json(json!({
+                    "status": "error",
+                    "message": format!("Unsupported currency code: {}", code)
+                }));
+            }
+        },
+        None => Currency::default(),
+    };
+    let max_price =
+        match PriceInput::to_optional_money(req_body.filters.max_price.as_ref(), currency) {
+            Some(max_price) => max_price,
+            None => {
+                return HttpResponse::BadRequest().json(json!({
+                    "status": "error",
+                    "message": format!("max_price must be a positive amount in {}", currency)
+                }));
+            }
+        };
+
+    let saved_search = SavedSearch {
+        id: None,
+        user_id,
+        query: query.to_string(),
+        filters: SavedSearchFilters {
+            category: req_body
+                .filters
+                .category
+                .as_ref()
+                .map(|category| category.trim().to_lowercase())
+                .filter(|category| !category.is_empty()),
+            auction_type: req_body.filters.auction_type.clone(),
+            max_price,
+        },
+        paused: false,
+        created_at: DateTime::from_chrono(Utc::now()),
+    };
+
+    let collection = mongo_client
+        .get_db()
+        .collection::<SavedSearch>("saved_searches");
+    let search_id = match collection.insert_one(&saved_search, None).await {
+        Ok(result) => match result.inserted_id.as_object_id() {
+            Some(search_id) => search_id,
+            None => {
+                return HttpResponse::InternalServerError().json(json!({
+                    "status": "error",
+                    "message": "Failed to save search"
+                }));
+            }
+        },
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB insert failed: {}", e),
+            }));
+        }
+    };
+
+    if let Err(err) = elasticsearch_client
+        .save_search(&search_id.to_hex(), query)
+        .await
+    {
+        eprintln!(
+            "Failed to register saved search in Elasticsearch: {:?}",
+            err
+        );
+        let _ = collection.delete_one(doc! {"_id": search_id}, None).await;
+        return HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": "Failed to save search"
+        }));
+    }
+
+    HttpResponse::Created().json(json!({
+        "status": "success",
+        "search_id": search_id.to_hex(),
+        "message": "Search saved, you will be alerted about new matching items"
+    }))
+}
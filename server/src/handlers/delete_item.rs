@@ -3,6 +3,7 @@ use bson::doc;
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::Principal,
     elasticsearch::ElasticSearchClient,
     mongo::{Item, MongoClient},
     redis::RedisClient,
@@ -11,7 +12,7 @@ use crate::{
 
 #[derive(Deserialize)]
 struct DeleteItemRequest {
-    seller: String,
+    seller: Option<String>,
 }
 
 #[derive(Deserialize, Serialize)]
@@ -35,6 +36,7 @@ struct BlockchainDeleteResponse {
 
 #[delete("/api/v1/item/{item_id}")]
 pub async fn delete_item_handler(
+    principal: Principal,
     item_id: web::Path<String>,
     req_body: web::Json<DeleteItemRequest>,
     mongo_client: web::Data<MongoClient>,
@@ -43,13 +45,39 @@ pub async fn delete_item_handler(
     blockchain_api_base_uri: web::Data<BlockchainAPIURI>,
 ) -> impl Responder {
     let item_id = item_id.into_inner();
-    let seller = req_body.seller.clone();
+    let seller = match principal.resolve(req_body.seller.as_deref()) {
+        Ok(seller) => seller,
+        Err(err) => return err.error_response(),
+    };
     let blockchain_api_base_uri = &blockchain_api_base_uri.uri;
 
+    match mongo_client
+        .get_db()
+        .collection::<Item>("items")
+        .find_one(doc! {"_id": &item_id}, None)
+        .await
+    {
+        Ok(Some(item)) if item.seller.is_some() && !item.is_seller(&seller) => {
+            return HttpResponse::Forbidden().json(DeleteItemResponse {
+                status: "error".to_string(),
+                operation_id: None,
+                message: "Only the seller can delete this item".to_string(),
+            });
+        }
+        Ok(_) => {}
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(DeleteItemResponse {
+                status: "error".to_string(),
+                operation_id: None,
+                message: "Failed to check item ownership".to_string(),
+            });
+        }
+    }
+
     let delete_req = BlockchainDeleteRequest { seller };
 
     let blockchain_response = match reqwest::Client::new()
-        .delete(&format!("{}/item/{}", blockchain_api_base_uri, item_id))
+        .delete(format!("{}/item/{}", blockchain_api_base_uri, item_id))
         .json(&delete_req)
         .send()
         .await
@@ -138,11 +166,11 @@ pub async fn delete_item_handler(
                     );
 
                     eprintln!("{}", error);
-                    return HttpResponse::InternalServerError().json(DeleteItemResponse {
+                    HttpResponse::InternalServerError().json(DeleteItemResponse {
                         status: "error".to_string(),
                         operation_id: None,
                         message: error,
-                    });
+                    })
                 });
 
             let collection = mongo_client.get_db().collection::<Item>("items");
@@ -158,11 +186,11 @@ pub async fn delete_item_handler(
                     if let Err(err) = elasticsearch_client.remove_item(&item_id).await {
                         eprintln!("Failed to remove item from elastic search : {:?}", err);
                     };
-                    return HttpResponse::Ok().json(DeleteItemResponse {
+                    HttpResponse::Ok().json(DeleteItemResponse {
                         status: "success".to_string(),
                         operation_id,
                         message: "Item Will Be Deleted Shortly".to_string(),
-                    });
+                    })
                 }
                 Err(_) => HttpResponse::InternalServerError().json(DeleteItemResponse {
                     status: "error".to_string(),
@@ -0,0 +1,75 @@
+use actix_web::{delete, web, HttpResponse, Responder};
+use bson::{doc, oid::ObjectId};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    elasticsearch::ElasticSearchClient,
+    mongo::{MongoClient, SavedSearch},
+};
+
+#[derive(Deserialize)]
+struct DeleteSavedSearchRequest {
+    user_id: Option<String>,
+}
+
+#[delete("/api/v1/saved-searches/{search_id}")]
+pub async fn delete_saved_search_handler(
+    principal: Principal,
+    search_id: web::Path<String>,
+    req_body: web::Json<DeleteSavedSearchRequest>,
+    mongo_client: web::Data<MongoClient>,
+    elasticsearch_client: web::Data<ElasticSearchClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(req_body.user_id.as_deref()) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+    let search_id = match ObjectId::parse_str(search_id.into_inner()) {
+        Ok(search_id) => search_id,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": "Invalid search id"
+            }));
+        }
+    };
+
+    match mongo_client
+        .get_db()
+        .collection::<SavedSearch>("saved_searches")
+        .delete_one(doc! {"_id": search_id, "user_id": &user_id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count == 0 => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": "Saved search not found"
+            }));
+        }
+        Ok(_) => {}
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB delete failed: {}", e),
+            }));
+        }
+    }
+
+    // A leftover percolator query is harmless: matches without a saved search are ignored.
+    if let Err(err) = elasticsearch_client
+        .remove_saved_search(&search_id.to_hex())
+        .await
+    {
+        eprintln!(
+            "Failed to remove saved search from Elasticsearch: {:?}",
+            err
+        );
+    }
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "message": "Saved search deleted"
+    }))
+}
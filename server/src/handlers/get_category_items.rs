@@ -2,6 +2,8 @@ use actix_web::{get, web, HttpResponse, Responder};
 use futures::StreamExt;
 use mongodb::bson::doc;
 
+use super::item_view::public_items;
+
 #[get("/api/v1/items/category/{category_name}")]
 pub async fn get_category_items_handler(
     path: web::Path<String>,
@@ -17,7 +19,7 @@ pub async fn get_category_items_handler(
     {
         return HttpResponse::Ok().json(serde_json::json!({
             "status": "success",
-            "items": cached_items,
+            "items": public_items(&redis_client, &cached_items).await,
         }));
     }
 
@@ -25,14 +27,11 @@ pub async fn get_category_items_handler(
         .get_db()
         .collection::<crate::mongo::Item>("items");
 
+    let mut category_filter = crate::mongo::open_auction_filter();
+    category_filter.insert("category", &category_name);
+
     let category_items_count = match items_collection
-        .count_documents(
-            doc! {
-                "category": &category_name,
-                "status": crate::mongo::ItemStatus::ACTIVE.to_string(),
-            },
-            None,
-        )
+        .count_documents(category_filter.clone(), None)
         .await
     {
         Ok(count) => count,
@@ -53,10 +52,7 @@ pub async fn get_category_items_handler(
 
     let pipeline = vec![
         doc! {
-            "$match": {
-                "category": &category_name,
-                "status": crate::mongo::ItemStatus::ACTIVE.to_string(),
-            }
+            "$match": category_filter
         },
         doc! {
             "$lookup": {
@@ -115,6 +111,6 @@ pub async fn get_category_items_handler(
 
     HttpResponse::Ok().json(serde_json::json!({
         "status": "success",
-        "items": items,
+        "items": public_items(&redis_client, &items).await,
     }))
 }
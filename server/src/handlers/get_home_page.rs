@@ -3,12 +3,28 @@ use futures::StreamExt;
 use mongodb::bson::{doc, Document};
 use serde::{Deserialize, Serialize};
 
+use super::item_view::public_items;
+
 #[derive(Serialize, Deserialize)]
 struct HomeCategory {
     category: String,
     items: Vec<crate::mongo::Item>,
 }
 
+async fn public_home_categories(
+    redis_client: &crate::redis::RedisClient,
+    home_categories: &[HomeCategory],
+) -> Vec<serde_json::Value> {
+    let mut public_categories = Vec::with_capacity(home_categories.len());
+    for home_category in home_categories {
+        public_categories.push(serde_json::json!({
+            "category": home_category.category,
+            "items": public_items(redis_client, &home_category.items).await,
+        }));
+    }
+    public_categories
+}
+
 #[get("/api/v1/home")]
 pub async fn get_home_page_handler(
     mongo_client: web::Data<crate::mongo::MongoClient>,
@@ -21,7 +37,7 @@ pub async fn get_home_page_handler(
     {
         return HttpResponse::Ok().json(serde_json::json!({
             "status": "success",
-            "home": cached_categories,
+            "home": public_home_categories(&redis_client, &cached_categories).await,
         }));
     }
 
@@ -29,10 +45,7 @@ pub async fn get_home_page_handler(
 
     let items_collection = db.collection::<crate::mongo::Item>("items");
     let active_count = match items_collection
-        .count_documents(
-            doc! { "status": crate::mongo::ItemStatus::ACTIVE.to_string() },
-            None,
-        )
+        .count_documents(crate::mongo::open_auction_filter(), None)
         .await
     {
         Ok(count) => count,
@@ -53,9 +66,7 @@ pub async fn get_home_page_handler(
 
     let pipeline = vec![
         doc! {
-            "$match": {
-                "status": crate::mongo::ItemStatus::ACTIVE.to_string()
-            }
+            "$match": crate::mongo::open_auction_filter()
         },
         doc! {
             "$group": {
@@ -159,7 +170,7 @@ pub async fn get_home_page_handler(
         if let Ok(category_str) = doc.get_str("category") {
             let category = category_str.to_string();
 
-            if let Some(items_array) = doc.get_array("items").ok() {
+            if let Ok(items_array) = doc.get_array("items") {
                 let mut category_items = Vec::new();
 
                 for item_bson in items_array {
@@ -191,6 +202,6 @@ pub async fn get_home_page_handler(
 
     HttpResponse::Ok().json(serde_json::json!({
         "status": "success",
-        "home": home_categories,
+        "home": public_home_categories(&redis_client, &home_categories).await,
     }))
 }
@@ -3,15 +3,18 @@ use actix_web::{
     web::{self},
     HttpResponse, Responder,
 };
+use chrono::Utc;
 use futures::StreamExt;
 use mongodb::bson::doc;
 use serde_json::json;
 use std::collections::HashMap;
 
+use super::item_view::public_items;
 use crate::{
+    auth::Principal,
     mongo::{Item, MongoClient},
     redis::RedisClient,
-    types::CurrentBid,
+    types::{BiddingConfig, CurrentBid},
 };
 
 #[get("/api/v1/item")]
@@ -19,6 +22,8 @@ pub async fn get_item_handler(
     web::Query(params): web::Query<HashMap<String, String>>,
     redis_client: web::Data<RedisClient>,
     mongo_client: web::Data<MongoClient>,
+    bidding_config: web::Data<BiddingConfig>,
+    principal: Option<Principal>,
 ) -> impl Responder {
     let item_id = match params.get("item_id") {
         Some(id) => id,
@@ -30,10 +35,10 @@ pub async fn get_item_handler(
         }
     };
 
-    let item_details: Option<Item> = match redis_client.get_value("item_details", &item_id).await {
-        Ok(details) => details,
-        Err(_) => None,
-    };
+    let item_details: Option<Item> = redis_client
+        .get_value("item_details", item_id)
+        .await
+        .unwrap_or_default();
 
     let item_details = match item_details {
         Some(details) => details,
@@ -42,7 +47,7 @@ pub async fn get_item_handler(
             match collection.find_one(doc! {"_id": item_id}, None).await {
                 Ok(Some(item)) => {
                     redis_client
-                        .set_value("item_details", &item_id, &item, Some(3600))
+                        .set_value("item_details", item_id, &item, Some(3600))
                         .await
                         .ok();
                     item
@@ -57,18 +62,33 @@ pub async fn get_item_handler(
         }
     };
 
-    let item_current_bid: Option<CurrentBid> =
-        match redis_client.get_value("current_bid", &item_id).await {
-            Ok(current_bid) => current_bid,
-            Err(_) => None,
-        };
+    let item_current_bid: Option<CurrentBid> = redis_client
+        .get_value("current_bid", item_id)
+        .await
+        .unwrap_or_default();
 
-    let item_current_bid_price = match item_current_bid {
-        Some(current_bid) => current_bid.bid_price,
-        None => -1.0,
-    };
+    // Sealed bids never reach the current bid cache, so their price only shows
+    // up once the worker has settled the clearing price at close.
+    let current_bid_price = item_current_bid
+        .map(|current_bid| current_bid.bid_price)
+        .or(item_details.clearing_price);
+
+    // Dutch auctions have no bid ladder: until someone accepts, the price is
+    // whatever the listing schedule has dropped to by now.
+    let (item_current_bid_price, next_bid_price) =
+        match (item_details.dutch_price(Utc::now()), current_bid_price) {
+            (Some(dutch_price), None) => (Some(dutch_price), dutch_price),
+            _ => (
+                current_bid_price,
+                bidding_config.increments.next_bid_price(
+                    &item_details.category,
+                    current_bid_price,
+                    item_details.base_price,
+                ),
+            ),
+        };
 
-    let similar_item_ids: Vec<String> = match redis_client.get_similar_items(&item_id).await {
+    let similar_item_ids: Vec<String> = match redis_client.get_similar_items(item_id).await {
         Ok(Some(ids)) => ids,
         _ => vec![],
     };
@@ -89,7 +109,7 @@ pub async fn get_item_handler(
             .filter(|id| {
                 !final_similar_items
                     .iter()
-                    .any(|item| item.id.clone().map_or(false, |i| i.to_string() == **id))
+                    .any(|item| item.id.as_deref() == Some(id.as_str()))
             })
             .cloned()
             .collect();
@@ -117,10 +137,35 @@ pub async fn get_item_handler(
         }
     }
 
+    let is_seller = match &principal {
+        Some(principal) if item_details.seller.is_some() => {
+            item_details.is_seller(&principal.user_id)
+        }
+        Some(principal) => match mongo_client.find_item_seller(item_id).await {
+            Ok(seller) => {
+                seller.is_some_and(|seller| seller.eq_ignore_ascii_case(&principal.user_id))
+            }
+            Err(err) => {
+                eprintln!("Failed to look up seller for item {}: {:?}", item_id, err);
+                false
+            }
+        },
+        None => false,
+    };
+
+    // The reserve price stays hidden from everyone but the seller.
+    let reserve_price = item_details.reserve_price.filter(|_| is_seller);
+    let mut item_details = item_details.to_public_json(current_bid_price);
+    if let (Some(reserve_price), Some(fields)) = (reserve_price, item_details.as_object_mut()) {
+        fields.insert("reserve_price".to_string(), json!(reserve_price));
+    }
+    let final_similar_items = public_items(&redis_client, &final_similar_items).await;
+
     HttpResponse::Ok().json(json!({
         "status": "success",
         "item_details": item_details,
         "current_bid_price" : item_current_bid_price,
+        "next_bid_price": next_bid_price,
         "similar_items_details": final_similar_items
     }))
 }
@@ -0,0 +1,251 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::{doc, oid::ObjectId, DateTime, Document};
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use super::item_view::mask_bidder;
+use crate::auth::Principal;
+use crate::money::Money;
+use crate::mongo::{Bid, Item, ItemStatus, MongoClient};
+
+const DEFAULT_PAGE_SIZE: i64 = 20;
+const MAX_PAGE_SIZE: i64 = 100;
+
+#[derive(Deserialize)]
+struct BidHistoryParams {
+    cursor: Option<String>,
+    limit: Option<i64>,
+}
+
+#[derive(Serialize)]
+struct BidHistoryEntry {
+    bidder: String,
+    bid_price: Money,
+    timestamp: String,
+    proxy: bool,
+    buy_now: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    quantity: Option<u32>,
+}
+
+#[derive(Serialize, Default)]
+struct BidHistorySummary {
+    bid_count: i64,
+    unique_bidders: i64,
+    first_bid_at: Option<String>,
+    last_bid_at: Option<String>,
+}
+
+fn encode_cursor(bid: &Bid) -> Option<String> {
+    bid.id
+        .map(|id| format!("{}_{}", bid.timestamp.timestamp_millis(), id.to_hex()))
+}
+
+fn decode_cursor(cursor: &str) -> Option<(DateTime, ObjectId)> {
+    let (timestamp, id) = cursor.split_once('_')?;
+    let timestamp = DateTime::from_millis(timestamp.parse().ok()?);
+    let id = ObjectId::parse_str(id).ok()?;
+    Some((timestamp, id))
+}
+
+async fn bid_history_summary(
+    mongo_client: &MongoClient,
+    item_id: &str,
+) -> Result<BidHistorySummary, mongodb::error::Error> {
+    let pipeline = vec![
+        doc! {"$match": {"item_id": item_id}},
+        doc! {
+            "$group": {
+                "_id": null,
+                "bid_count": {"$sum": 1},
+                "bidders": {"$addToSet": "$bidder"},
+                "first_bid_at": {"$min": "$timestamp"},
+                "last_bid_at": {"$max": "$timestamp"},
+            }
+        },
+        doc! {
+            "$project": {
+                "_id": 0,
+                "bid_count": 1,
+                "unique_bidders": {"$size": "$bidders"},
+                "first_bid_at": 1,
+                "last_bid_at": 1,
+            }
+        },
+    ];
+
+    let mut cursor = mongo_client
+        .get_db()
+        .collection::<Bid>("bids")
+        .aggregate(pipeline, None)
+        .await?;
+
+    let summary: Option<Document> = cursor.try_next().await?;
+    Ok(summary
+        .map(|summary| BidHistorySummary {
+            bid_count: summary.get_i32("bid_count").map(i64::from).unwrap_or(0),
+            unique_bidders: summary
+                .get_i32("unique_bidders")
+                .map(i64::from)
+                .unwrap_or(0),
+            first_bid_at: summary
+                .get_datetime("first_bid_at")
+                .ok()
+                .map(|timestamp| timestamp.to_chrono().to_rfc3339()),
+            last_bid_at: summary
+                .get_datetime("last_bid_at")
+                .ok()
+                .map(|timestamp| timestamp.to_chrono().to_rfc3339()),
+        })
+        .unwrap_or_default())
+}
+
+#[get("/api/v1/item/{item_id}/bids")]
+pub async fn get_item_bids_handler(
+    item_id: web::Path<String>,
+    query: web::Query<BidHistoryParams>,
+    mongo_client: web::Data<MongoClient>,
+    principal: Option<Principal>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let mut filter = doc! {"item_id": &item_id};
+    if let Some(cursor) = &query.cursor {
+        match decode_cursor(cursor) {
+            Some((timestamp, id)) => {
+                filter.insert(
+                    "$or",
+                    vec![
+                        doc! {"timestamp": {"$lt": timestamp}},
+                        doc! {"timestamp": timestamp, "_id": {"$lt": id}},
+                    ],
+                );
+            }
+            None => {
+                return HttpResponse::BadRequest().json(json!({
+                    "status": "error",
+                    "message": "Invalid cursor parameter"
+                }));
+            }
+        }
+    }
+
+    let item = match mongo_client
+        .get_db()
+        .collection::<Item>("items")
+        .find_one(doc! {"_id": &item_id}, None)
+        .await
+    {
+        Ok(Some(item)) => item,
+        Ok(None) => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": "Item not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB query failed: {}", e),
+            }));
+        }
+    };
+
+    if item.auction_type.is_sealed()
+        && matches!(
+            item.status,
+            ItemStatus::PENDING | ItemStatus::SCHEDULED | ItemStatus::ACTIVE
+        )
+    {
+        return HttpResponse::Forbidden().json(json!({
+            "status": "error",
+            "message": "Bids are sealed until the auction closes"
+        }));
+    }
+
+    let is_seller = match &principal {
+        Some(principal) => match mongo_client.find_item_seller(&item_id).await {
+            Ok(seller) => {
+                seller.is_some_and(|seller| seller.eq_ignore_ascii_case(&principal.user_id))
+            }
+            Err(err) => {
+                eprintln!("Failed to look up seller for item {}: {:?}", item_id, err);
+                false
+            }
+        },
+        None => false,
+    };
+
+    let find_options = FindOptions::builder()
+        .sort(doc! {"timestamp": -1, "_id": -1})
+        .limit(limit)
+        .build();
+
+    let bids: Vec<Bid> = match mongo_client
+        .get_db()
+        .collection::<Bid>("bids")
+        .find(filter, find_options)
+        .await
+    {
+        Ok(cursor) => match cursor.try_collect().await {
+            Ok(bids) => bids,
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(json!({
+                    "status": "error",
+                    "message": format!("MongoDB query failed: {}", e),
+                }));
+            }
+        },
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB query failed: {}", e),
+            }));
+        }
+    };
+
+    let summary = match bid_history_summary(&mongo_client, &item_id).await {
+        Ok(summary) => summary,
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB aggregation failed: {}", e),
+            }));
+        }
+    };
+
+    let next_cursor = if bids.len() as i64 == limit {
+        bids.last().and_then(encode_cursor)
+    } else {
+        None
+    };
+
+    let entries: Vec<BidHistoryEntry> = bids
+        .into_iter()
+        .map(|bid| BidHistoryEntry {
+            bidder: if is_seller {
+                bid.bidder
+            } else {
+                mask_bidder(&bid.bidder)
+            },
+            bid_price: bid.bid_price,
+            timestamp: bid.timestamp.to_chrono().to_rfc3339(),
+            proxy: bid.proxy,
+            buy_now: bid.buy_now,
+            quantity: bid.quantity,
+        })
+        .collect();
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "bids": entries,
+        "next_cursor": next_cursor,
+        "summary": summary,
+    }))
+}
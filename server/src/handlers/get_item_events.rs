@@ -0,0 +1,61 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::redis::RedisClient;
+
+const DEFAULT_EVENT_LIMIT: usize = 100;
+const MAX_EVENT_LIMIT: usize = 500;
+
+#[derive(Deserialize)]
+struct ItemEventsParams {
+    since: Option<u64>,
+    limit: Option<usize>,
+}
+
+#[get("/api/v1/item/{item_id}/events")]
+pub async fn get_item_events_handler(
+    item_id: web::Path<String>,
+    query: web::Query<ItemEventsParams>,
+    redis_client: web::Data<RedisClient>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let since = query.since.unwrap_or(0);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_EVENT_LIMIT)
+        .clamp(1, MAX_EVENT_LIMIT);
+
+    let entries = match redis_client.read_events(&item_id, since, limit).await {
+        Ok(entries) => entries,
+        Err(err) => {
+            eprintln!(
+                "Failed to read bid events for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": "Failed to read bid events"
+            }));
+        }
+    };
+
+    // Sequences are contiguous, so a gap right after `since` means the oldest
+    // events were trimmed from the log and the client should refetch the item.
+    let truncated = entries
+        .first()
+        .is_some_and(|(sequence, _)| *sequence > since + 1);
+    let last_sequence = entries.last().map_or(since, |(sequence, _)| *sequence);
+
+    let events: Vec<Value> = entries
+        .into_iter()
+        .filter_map(|(_, payload)| serde_json::from_str(&payload).ok())
+        .collect();
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "events": events,
+        "last_sequence": last_sequence,
+        "truncated": truncated,
+    }))
+}
@@ -0,0 +1,22 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde_json::json;
+
+use crate::receipts::ReceiptSigner;
+
+/// Publishes the public half of the receipt signing key as a JSON Web Key Set.
+#[get("/.well-known/jwks.json")]
+pub async fn get_receipt_keys_handler(receipt_signer: web::Data<ReceiptSigner>) -> impl Responder {
+    HttpResponse::Ok()
+        .insert_header(("Cache-Control", "public, max-age=3600"))
+        .json(json!({
+            "keys": [{
+                "kty": "OKP",
+                "crv": "Ed25519",
+                "alg": "EdDSA",
+                "use": "sig",
+                "kid": receipt_signer.key_id(),
+                "x": URL_SAFE_NO_PAD.encode(receipt_signer.public_key().as_bytes()),
+            }]
+        }))
+}
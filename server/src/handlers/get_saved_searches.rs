@@ -0,0 +1,55 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{MongoClient, SavedSearch},
+};
+
+#[get("/api/v1/saved-searches/{user_id}")]
+pub async fn get_saved_searches_handler(
+    principal: Principal,
+    user_id: web::Path<String>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(Some(&user_id)) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+
+    let find_options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+    match mongo_client
+        .get_db()
+        .collection::<SavedSearch>("saved_searches")
+        .find(doc! {"user_id": &user_id}, find_options)
+        .await
+    {
+        Ok(cursor) => {
+            let saved_searches: Vec<SavedSearch> = cursor.try_collect().await.unwrap_or_default();
+            let saved_searches: Vec<serde_json::Value> = saved_searches
+                .into_iter()
+                .map(|search| {
+                    json!({
+                        "search_id": search.id.map(|id| id.to_hex()),
+                        "query": search.query,
+                        "filters": search.filters,
+                        "paused": search.paused,
+                        "created_at": search.created_at.to_chrono().to_rfc3339(),
+                    })
+                })
+                .collect();
+
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "saved_searches": saved_searches,
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": format!("MongoDB query failed: {}", e),
+        })),
+    }
+}
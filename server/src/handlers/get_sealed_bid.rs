@@ -0,0 +1,78 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::doc;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{Bid, Item, MongoClient},
+};
+
+#[derive(Deserialize)]
+struct SealedBidParams {
+    bidder: Option<String>,
+}
+
+#[get("/api/v1/item/{item_id}/sealed-bid")]
+pub async fn get_sealed_bid_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    query: web::Query<SealedBidParams>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let bidder = match principal.resolve(query.bidder.as_deref()) {
+        Ok(bidder) => bidder,
+        Err(err) => return err.error_response(),
+    };
+    let db = mongo_client.get_db();
+
+    match db
+        .collection::<Item>("items")
+        .find_one(doc! {"_id": &item_id}, None)
+        .await
+    {
+        Ok(Some(item)) if item.auction_type.is_sealed() => {}
+        Ok(Some(_)) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": "Item is not a sealed-bid auction"
+            }));
+        }
+        Ok(None) => {
+            return HttpResponse::NotFound().json(json!({
+                "status": "error",
+                "message": "Item not found"
+            }));
+        }
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB query failed: {}", e),
+            }));
+        }
+    }
+
+    match db
+        .collection::<Bid>("bids")
+        .find_one(doc! {"item_id": &item_id, "bidder": &bidder}, None)
+        .await
+    {
+        Ok(Some(bid)) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "bid": {
+                "bid_id": bid.id.map(|id| id.to_hex()),
+                "bid_price": bid.bid_price,
+                "timestamp": bid.timestamp.to_chrono().to_rfc3339(),
+            }
+        })),
+        Ok(None) => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "No sealed bid found for this bidder"
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": format!("MongoDB query failed: {}", e),
+        })),
+    }
+}
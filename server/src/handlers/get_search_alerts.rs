@@ -0,0 +1,71 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::doc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{MongoClient, SearchAlert},
+};
+
+const DEFAULT_ALERT_LIMIT: i64 = 50;
+const MAX_ALERT_LIMIT: i64 = 200;
+
+#[derive(Deserialize)]
+struct SearchAlertsParams {
+    limit: Option<i64>,
+}
+
+#[get("/api/v1/saved-searches/{user_id}/alerts")]
+pub async fn get_search_alerts_handler(
+    principal: Principal,
+    user_id: web::Path<String>,
+    query: web::Query<SearchAlertsParams>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(Some(&user_id)) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ALERT_LIMIT)
+        .clamp(1, MAX_ALERT_LIMIT);
+
+    let find_options = FindOptions::builder()
+        .sort(doc! {"created_at": -1})
+        .limit(limit)
+        .build();
+    match mongo_client
+        .get_db()
+        .collection::<SearchAlert>("search_alerts")
+        .find(doc! {"user_id": &user_id}, find_options)
+        .await
+    {
+        Ok(cursor) => {
+            let alerts: Vec<SearchAlert> = cursor.try_collect().await.unwrap_or_default();
+            let alerts: Vec<serde_json::Value> = alerts
+                .into_iter()
+                .map(|alert| {
+                    json!({
+                        "search_id": alert.saved_search_id.to_hex(),
+                        "item_id": alert.item_id,
+                        "item_title": alert.item_title,
+                        "created_at": alert.created_at.to_chrono().to_rfc3339(),
+                    })
+                })
+                .collect();
+
+            HttpResponse::Ok().json(json!({
+                "status": "success",
+                "alerts": alerts,
+            }))
+        }
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": format!("MongoDB query failed: {}", e),
+        })),
+    }
+}
@@ -31,9 +31,7 @@ pub async fn get_top_categories_handler(
     let db = mongo_client.get_db();
     let pipeline = vec![
         doc! {
-            "$match": {
-                "status": crate::mongo::ItemStatus::ACTIVE.to_string()
-            }
+            "$match": crate::mongo::open_auction_filter()
         },
         doc! {
             "$group": {
@@ -1,4 +1,5 @@
 use crate::{
+    auth::Principal,
     mongo::{Item, MongoClient},
     redis::RedisClient,
     types::BlockchainAPIURI,
@@ -8,6 +9,8 @@ use futures::TryStreamExt;
 use mongodb::bson::doc;
 use serde::{Deserialize, Serialize};
 
+use super::item_view::public_items;
+
 #[derive(Deserialize, Serialize)]
 struct BlockchainResponse {
     status: String,
@@ -21,15 +24,13 @@ pub async fn get_user_items_handler(
     redis_client: web::Data<RedisClient>,
     mongo_client: web::Data<MongoClient>,
     blockchain_api_base_uri: web::Data<BlockchainAPIURI>,
+    principal: Option<Principal>,
 ) -> impl Responder {
     let user_id = user_id.into_inner();
     let blockchain_api_base_uri = &blockchain_api_base_uri.uri;
 
     let blockchain_response = match reqwest::Client::new()
-        .get(&format!(
-            "{}/userItems/{}",
-            blockchain_api_base_uri, user_id
-        ))
+        .get(format!("{}/userItems/{}", blockchain_api_base_uri, user_id))
         .send()
         .await
     {
@@ -62,6 +63,13 @@ pub async fn get_user_items_handler(
                 }
             };
 
+            if let Err(err) = mongo_client.backfill_item_sellers(&user_id, item_ids).await {
+                eprintln!(
+                    "Failed to backfill seller for user {}. Error: {:?}",
+                    user_id, err
+                );
+            }
+
             let all_items_details: Vec<Item> = redis_client
                 .get_items_details_json(item_ids.clone())
                 .await
@@ -78,9 +86,9 @@ pub async fn get_user_items_handler(
                     let missing_ids: Vec<String> = blockchain_items
                         .iter()
                         .filter(|id| {
-                            !final_items.iter().any(|item| {
-                                item.id.clone().map_or(false, |i| i.to_string() == **id)
-                            })
+                            !final_items
+                                .iter()
+                                .any(|item| item.id.as_deref() == Some(id.as_str()))
                         })
                         .cloned()
                         .collect();
@@ -123,9 +131,57 @@ pub async fn get_user_items_handler(
                 }
             }
 
+            // Reserve prices and watcher counts are only for the seller's eyes.
+            let is_seller = |item: &Item| {
+                principal
+                    .as_ref()
+                    .is_some_and(|principal| item.is_seller(&principal.user_id))
+            };
+            let seller_item_ids: Vec<String> = final_items
+                .iter()
+                .filter(|item| is_seller(item))
+                .filter_map(|item| item.id.clone())
+                .collect();
+            let watcher_counts = if seller_item_ids.is_empty() {
+                Default::default()
+            } else {
+                match mongo_client.count_watchers(&seller_item_ids).await {
+                    Ok(counts) => counts,
+                    Err(err) => {
+                        eprintln!(
+                            "Failed to count watchers for user {}. Error: {:?}",
+                            user_id, err
+                        );
+                        Default::default()
+                    }
+                }
+            };
+
+            let items: Vec<serde_json::Value> = public_items(&redis_client, &final_items)
+                .await
+                .into_iter()
+                .zip(&final_items)
+                .map(|(public_item, item)| {
+                    if !is_seller(item) {
+                        return public_item;
+                    }
+                    let mut value = serde_json::to_value(item).unwrap_or_default();
+                    if let Some(fields) = value.as_object_mut() {
+                        let watcher_count = item
+                            .id
+                            .as_ref()
+                            .and_then(|id| watcher_counts.get(id))
+                            .copied()
+                            .unwrap_or(0);
+                        fields.insert("watcher_count".to_string(), watcher_count.into());
+                    }
+                    value
+                })
+                .collect();
+
             HttpResponse::Ok().json(serde_json::json!({
                 "status": "success",
-                "items": final_items
+                "items": items
             }))
         }
         "error" => {
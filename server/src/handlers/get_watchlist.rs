@@ -0,0 +1,123 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use bson::doc;
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::options::FindOptions;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{Item, MongoClient, WatchlistEntry},
+    redis::RedisClient,
+    types::CurrentBid,
+};
+
+#[get("/api/v1/watchlist/{user_id}")]
+pub async fn get_watchlist_handler(
+    principal: Principal,
+    user_id: web::Path<String>,
+    mongo_client: web::Data<MongoClient>,
+    redis_client: web::Data<RedisClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(Some(&user_id)) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+    let db = mongo_client.get_db();
+
+    let find_options = FindOptions::builder().sort(doc! {"created_at": -1}).build();
+    let entries: Vec<WatchlistEntry> = match db
+        .collection::<WatchlistEntry>("watchlists")
+        .find(doc! {"user_id": &user_id}, find_options)
+        .await
+    {
+        Ok(cursor) => cursor.try_collect().await.unwrap_or_default(),
+        Err(e) => {
+            return HttpResponse::InternalServerError().json(json!({
+                "status": "error",
+                "message": format!("MongoDB query failed: {}", e),
+            }));
+        }
+    };
+
+    let item_ids: Vec<String> = entries.iter().map(|entry| entry.item_id.clone()).collect();
+
+    let mut items: Vec<Item> = redis_client
+        .get_items_details_json(item_ids.clone())
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .flatten()
+        .collect();
+
+    let missing_ids: Vec<&String> = item_ids
+        .iter()
+        .filter(|id| {
+            !items
+                .iter()
+                .any(|item| item.id.as_deref() == Some(id.as_str()))
+        })
+        .collect();
+
+    if !missing_ids.is_empty() {
+        match db
+            .collection::<Item>("items")
+            .find(doc! {"_id": {"$in": missing_ids}}, None)
+            .await
+        {
+            Ok(cursor) => {
+                let additional_items: Vec<Item> = cursor.try_collect().await.unwrap_or_default();
+                for item in &additional_items {
+                    if let Some(id) = &item.id {
+                        redis_client
+                            .set_value("item_details", id, item, Some(3600))
+                            .await
+                            .ok();
+                    }
+                }
+                items.extend(additional_items);
+            }
+            Err(e) => {
+                return HttpResponse::InternalServerError().json(json!({
+                    "status": "error",
+                    "message": format!("MongoDB query failed: {}", e),
+                }));
+            }
+        }
+    }
+
+    let current_bids: Vec<Option<CurrentBid>> = redis_client
+        .get_values("current_bid", &item_ids)
+        .await
+        .unwrap_or_default();
+
+    let now = Utc::now();
+    let watchlist: Vec<serde_json::Value> = entries
+        .iter()
+        .zip(
+            current_bids
+                .into_iter()
+                .chain(std::iter::repeat_with(|| None)),
+        )
+        .filter_map(|(entry, current_bid)| {
+            // Items deleted since they were watched are simply left out.
+            let item = items
+                .iter()
+                .find(|item| item.id.as_deref() == Some(entry.item_id.as_str()))?;
+            let current_bid_price = current_bid.map(|bid| bid.bid_price).or(item.clearing_price);
+            let time_left_seconds = (item.auction_end.to_chrono() - now).num_seconds().max(0);
+
+            Some(json!({
+                "item": item.to_public_json(current_bid_price),
+                "current_bid_price": current_bid_price,
+                "time_left_seconds": time_left_seconds,
+                "watched_at": entry.created_at.to_chrono().to_rfc3339(),
+            }))
+        })
+        .collect();
+
+    HttpResponse::Ok().json(json!({
+        "status": "success",
+        "items": watchlist,
+    }))
+}
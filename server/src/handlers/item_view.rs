@@ -0,0 +1,35 @@
+use serde_json::Value;
+
+use crate::{mongo::Item, redis::RedisClient, types::CurrentBid};
+
+pub(crate) fn mask_bidder(bidder: &str) -> String {
+    let chars: Vec<char> = bidder.chars().collect();
+    if chars.len() <= 6 {
+        return "***".to_string();
+    }
+    let prefix: String = chars[..4].iter().collect();
+    let suffix: String = chars[chars.len() - 2..].iter().collect();
+    format!("{}***{}", prefix, suffix)
+}
+
+pub(crate) async fn public_items(redis_client: &RedisClient, items: &[Item]) -> Vec<Value> {
+    let item_ids: Vec<String> = items
+        .iter()
+        .map(|item| item.id.clone().unwrap_or_default())
+        .collect();
+
+    let current_bids: Vec<Option<CurrentBid>> = redis_client
+        .get_values("current_bid", &item_ids)
+        .await
+        .unwrap_or_default();
+
+    items
+        .iter()
+        .zip(
+            current_bids
+                .into_iter()
+                .chain(std::iter::repeat_with(|| None)),
+        )
+        .map(|(item, current_bid)| item.to_public_json(current_bid.map(|bid| bid.bid_price)))
+        .collect()
+}
@@ -1,27 +1,74 @@
+mod add_to_watchlist;
+mod admin_ban_bidder;
+mod admin_end_auction;
+mod admin_get_failed_operations;
+mod admin_take_down_item;
+mod admin_unban_bidder;
+mod auction_close;
+mod auth_nonce;
+mod auth_verify;
 mod autocomplete_item;
+mod buy_now;
+mod create_saved_search;
 mod delete_item;
+mod delete_saved_search;
 mod get_category_items;
 mod get_home_page;
 mod get_item;
+mod get_item_bids;
+mod get_item_events;
 mod get_operation_status;
+mod get_receipt_keys;
+mod get_saved_searches;
+mod get_sealed_bid;
+mod get_search_alerts;
 mod get_top_categories;
 mod get_user_items;
+mod get_watchlist;
 mod health_check;
+mod item_view;
+mod pause_saved_search;
 mod place_bid;
 mod post_item;
+mod remove_from_watchlist;
+mod saved_search_alerts;
 mod search_item;
 mod transfer_item;
+mod verify_receipt;
 
+pub use add_to_watchlist::add_to_watchlist_handler;
+pub use admin_ban_bidder::admin_ban_bidder_handler;
+pub use admin_end_auction::admin_end_auction_handler;
+pub use admin_get_failed_operations::admin_get_failed_operations_handler;
+pub use admin_take_down_item::admin_take_down_item_handler;
+pub use admin_unban_bidder::admin_unban_bidder_handler;
+pub use auth_nonce::auth_nonce_handler;
+pub use auth_verify::auth_verify_handler;
 pub use autocomplete_item::autocomplete_item_handler;
+pub use buy_now::buy_now_handler;
+pub use create_saved_search::create_saved_search_handler;
 pub use delete_item::delete_item_handler;
+pub use delete_saved_search::delete_saved_search_handler;
 pub use get_category_items::get_category_items_handler;
 pub use get_home_page::get_home_page_handler;
 pub use get_item::get_item_handler;
+pub use get_item_bids::get_item_bids_handler;
+pub use get_item_events::get_item_events_handler;
 pub use get_operation_status::get_operation_status_handler;
+pub use get_receipt_keys::get_receipt_keys_handler;
+pub use get_saved_searches::get_saved_searches_handler;
+pub use get_sealed_bid::get_sealed_bid_handler;
+pub use get_search_alerts::get_search_alerts_handler;
 pub use get_top_categories::get_top_categories_handler;
 pub use get_user_items::get_user_items_handler;
+pub use get_watchlist::get_watchlist_handler;
 pub use health_check::health_check_handler;
+pub use pause_saved_search::pause_saved_search_handler;
 pub use place_bid::place_bid_handler;
 pub use post_item::post_item_handler;
+pub use remove_from_watchlist::remove_from_watchlist_handler;
 pub use search_item::search_item_handler;
 pub use transfer_item::transfer_item_handler;
+pub use verify_receipt::verify_receipt_handler;
+
+pub(crate) use saved_search_alerts::alert_saved_searches;
@@ -0,0 +1,73 @@
+use actix_web::{post, web, HttpResponse, Responder};
+use bson::{doc, oid::ObjectId};
+use serde::Deserialize;
+use serde_json::json;
+
+use crate::{
+    auth::Principal,
+    mongo::{MongoClient, SavedSearch},
+};
+
+fn default_paused() -> bool {
+    true
+}
+
+#[derive(Deserialize)]
+struct PauseSavedSearchRequest {
+    user_id: Option<String>,
+    #[serde(default = "default_paused")]
+    paused: bool,
+}
+
+/// Pauses a saved search, or resumes it when `paused` is false. Paused
+/// searches stay listed but do not produce alerts.
+#[post("/api/v1/saved-searches/{search_id}/pause")]
+pub async fn pause_saved_search_handler(
+    principal: Principal,
+    search_id: web::Path<String>,
+    req_body: web::Json<PauseSavedSearchRequest>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let user_id = match principal.resolve(req_body.user_id.as_deref()) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+    let search_id = match ObjectId::parse_str(search_id.into_inner()) {
+        Ok(search_id) => search_id,
+        Err(_) => {
+            return HttpResponse::BadRequest().json(json!({
+                "status": "error",
+                "message": "Invalid search id"
+            }));
+        }
+    };
+
+    match mongo_client
+        .get_db()
+        .collection::<SavedSearch>("saved_searches")
+        .update_one(
+            doc! {"_id": search_id, "user_id": &user_id},
+            doc! {"$set": {"paused": req_body.paused}},
+            None,
+        )
+        .await
+    {
+        Ok(result) if result.matched_count == 0 => HttpResponse::NotFound().json(json!({
+            "status": "error",
+            "message": "Saved search not found"
+        })),
+        Ok(_) => HttpResponse::Ok().json(json!({
+            "status": "success",
+            "paused": req_body.paused,
+            "message": if req_body.paused {
+                "Saved search paused"
+            } else {
+                "Saved search resumed"
+            }
+        })),
+        Err(e) => HttpResponse::InternalServerError().json(json!({
+            "status": "error",
+            "message": format!("MongoDB update failed: {}", e),
+        })),
+    }
+}
@@ -1,16 +1,52 @@
-use crate::mongo::{Bid, Item, MongoClient};
-use crate::redis::RedisClient;
-use crate::types::{CurrentBid, MessageToPublish};
+use super::auction_close::{close_auction_with_winner, AuctionCloseError};
+use super::item_view::mask_bidder;
+use crate::auth::Principal;
+use crate::elasticsearch::ElasticSearchClient;
+use crate::money::{Money, PriceInput};
+use crate::mongo::{
+    AuctionType, Bid, BidSignature, Item, ItemStatus, LotAllocation, LotBid, MongoClient, ProxyBid,
+};
+use crate::notifications::queue_outbid_notification;
+use crate::receipts::{BidReceipt, ReceiptSigner, SignedReceipt};
+use crate::redis::{RedisClient, RedisClientError};
+use crate::siwe;
+use crate::types::{
+    BidEvent, BidEventKind, BidIncrementRules, BidRateLimitConfig, BiddingConfig, CurrentBid,
+    SignedBidConfig, TransferSchedulerURI, BID_EVENT_VERSION,
+};
 use actix_web::{post, web, HttpResponse, Responder};
-use bson::{doc, DateTime};
+use bson::{doc, oid::ObjectId, DateTime};
 use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    options::{FindOptions, UpdateOptions},
+    Collection, Database,
+};
 use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+const MAX_PROXY_CONFLICT_RETRIES: usize = 5;
+const BID_NONCE_PREFIX: &str = "bid_nonce";
 
 #[derive(Deserialize)]
 pub struct PlaceBidRequest {
     item_id: String,
-    incrementation: f64,
-    bidder: String,
+    incrementation: Option<PriceInput>,
+    max_bid: Option<PriceInput>,
+    bid_price: Option<PriceInput>,
+    quantity: Option<u32>,
+    bidder: Option<String>,
+    authorization: Option<BidAuthorization>,
+}
+
+/// A wallet signature over `bid_authorization_message`, authorising bids on
+/// the item up to `amount`.
+#[derive(Deserialize)]
+pub struct BidAuthorization {
+    amount: PriceInput,
+    nonce: String,
+    expires_at: String,
+    signature: String,
 }
 
 #[derive(Serialize)]
@@ -19,29 +55,1059 @@ pub struct PlaceBidResponse {
     message: String,
 }
 
+#[derive(Serialize)]
+pub struct AcceptedBidResponse {
+    status: String,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    receipt: Option<SignedReceipt>,
+}
+
+/// Answers an accepted bid with a receipt for it, signed by the server.
+fn accepted_bid_response(
+    receipt_signer: &ReceiptSigner,
+    bid: Option<&Bid>,
+    message: String,
+) -> HttpResponse {
+    HttpResponse::Ok().json(AcceptedBidResponse {
+        status: "success".to_string(),
+        message,
+        receipt: bid
+            .and_then(BidReceipt::from_bid)
+            .map(|receipt| receipt_signer.sign(receipt)),
+    })
+}
+
+#[derive(Debug)]
+enum BidPlacementError {
+    Outbid,
+    CacheError,
+    DatabaseError,
+}
+
+#[derive(Debug)]
+pub(super) enum BidAuthorizationError {
+    Invalid(&'static str),
+    SignerMismatch,
+    NonceUsed,
+    CacheError,
+}
+
+impl BidAuthorizationError {
+    pub(super) fn error_response(&self) -> HttpResponse {
+        let (mut response, message) = match self {
+            BidAuthorizationError::Invalid(message) => (
+                HttpResponse::BadRequest(),
+                format!("Invalid bid signature: {}", message),
+            ),
+            BidAuthorizationError::SignerMismatch => (
+                HttpResponse::Forbidden(),
+                "Bid was not signed by the bidder's wallet".to_string(),
+            ),
+            BidAuthorizationError::NonceUsed => (
+                HttpResponse::Conflict(),
+                "Bid signature nonce has already been used".to_string(),
+            ),
+            BidAuthorizationError::CacheError => (
+                HttpResponse::InternalServerError(),
+                "Failed to check bid signature nonce".to_string(),
+            ),
+        };
+        response.json(PlaceBidResponse {
+            status: "error".to_string(),
+            message,
+        })
+    }
+}
+
+/// The text a bidder signs with `personal_sign` to authorise a bid. The amount
+/// is written with the currency's minor digits, e.g. `1500.00 USD`.
+pub fn bid_authorization_message(
+    item_id: &str,
+    amount: Money,
+    nonce: &str,
+    expires_at: &str,
+) -> String {
+    format!(
+        "Authorize bid\nItem: {}\nAmount: {} {}\nNonce: {}\nExpires At: {}",
+        item_id, amount, amount.currency, nonce, expires_at
+    )
+}
+
+/// Checks a bid authorization against the bidder and item, then reserves its
+/// nonce so the same signature can never authorise another bid. The handler
+/// releases the nonce again if the bid is not accepted.
+pub(super) async fn verify_bid_authorization(
+    redis_client: &RedisClient,
+    signed_bid_config: &SignedBidConfig,
+    item: &Item,
+    bidder: &str,
+    authorization: &BidAuthorization,
+) -> Result<BidSignature, BidAuthorizationError> {
+    let item_id = item.id.as_deref().unwrap_or_default();
+    let amount = authorization
+        .amount
+        .to_money(item.currency())
+        .filter(Money::is_positive)
+        .ok_or(BidAuthorizationError::Invalid("amount must be positive"))?;
+
+    let nonce = authorization.nonce.trim();
+    if !(8..=64).contains(&nonce.len()) || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(BidAuthorizationError::Invalid(
+            "nonce must be 8 to 64 letters or digits",
+        ));
+    }
+
+    let expires_at = chrono::DateTime::parse_from_rfc3339(authorization.expires_at.trim())
+        .map_err(|_| BidAuthorizationError::Invalid("expires_at must be an RFC 3339 timestamp"))?
+        .with_timezone(&Utc);
+    let now = Utc::now();
+    if expires_at <= now {
+        return Err(BidAuthorizationError::Invalid("authorization has expired"));
+    }
+    if expires_at > now + chrono::Duration::seconds(signed_bid_config.max_validity_seconds) {
+        return Err(BidAuthorizationError::Invalid(
+            "authorization expires too far in the future",
+        ));
+    }
+
+    let message =
+        bid_authorization_message(item_id, amount, nonce, authorization.expires_at.trim());
+    let signer = siwe::recover_address(&message, &authorization.signature)
+        .map_err(|_| BidAuthorizationError::Invalid("signature could not be verified"))?;
+    if !signer.eq_ignore_ascii_case(bidder) {
+        return Err(BidAuthorizationError::SignerMismatch);
+    }
+
+    // Nonces only need remembering until the authorization expires.
+    let ttl_seconds = (expires_at - now).num_seconds().max(0) as u64 + 60;
+    match redis_client
+        .set_value_if_absent(
+            BID_NONCE_PREFIX,
+            &format!("{}:{}", signer, nonce),
+            &item_id,
+            ttl_seconds,
+        )
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => return Err(BidAuthorizationError::NonceUsed),
+        Err(_) => return Err(BidAuthorizationError::CacheError),
+    }
+
+    Ok(BidSignature {
+        signer,
+        amount,
+        nonce: nonce.to_string(),
+        expires_at: DateTime::from_chrono(expires_at),
+        message,
+        signature: authorization.signature.trim().to_string(),
+    })
+}
+
+/// Makes the nonce of a rejected bid's authorization usable again.
+pub(super) async fn release_bid_nonce(redis_client: &RedisClient, signature: &BidSignature) {
+    let key = format!(
+        "{}:{}:{}",
+        BID_NONCE_PREFIX, signature.signer, signature.nonce
+    );
+    if let Err(err) = redis_client.delete_key(&key).await {
+        eprintln!(
+            "Failed to release bid nonce {} for {}. Error: {:?}",
+            signature.nonce, signature.signer, err
+        );
+    }
+}
+
+pub(super) fn exceeds_authorization(signature: Option<&BidSignature>, amount: Money) -> bool {
+    signature.is_some_and(|signature| amount > signature.amount)
+}
+
+pub(super) fn authorization_exceeded_response(signature: Option<&BidSignature>) -> HttpResponse {
+    HttpResponse::BadRequest().json(PlaceBidResponse {
+        status: "error".to_string(),
+        message: format!(
+            "Bid exceeds the signed amount of {}",
+            signature
+                .map(|signature| signature.amount.to_string())
+                .unwrap_or_default()
+        ),
+    })
+}
+
+pub(super) fn signature_required_response() -> HttpResponse {
+    HttpResponse::BadRequest().json(PlaceBidResponse {
+        status: "error".to_string(),
+        message: "Bids on this item must include a wallet signed authorization".to_string(),
+    })
+}
+
+async fn check_bid_rate_limit(
+    redis_client: &RedisClient,
+    rate_limit_config: &BidRateLimitConfig,
+    bidder: &str,
+    item_id: &str,
+) -> Option<u64> {
+    let window_ms = rate_limit_config.window_seconds * 1000;
+    let limits = [
+        ("bidder", bidder, rate_limit_config.bidder_limit),
+        ("item", item_id, rate_limit_config.item_limit),
+    ];
+
+    for (scope, key, limit) in limits {
+        match redis_client
+            .sliding_window_hit(&format!("bid_rate:{}:{}", scope, key), window_ms, limit)
+            .await
+        {
+            Ok(None) => {}
+            Ok(Some(retry_after_ms)) => {
+                eprintln!(
+                    "Bid rate limit hit for {} {} (bidder: {}, item: {})",
+                    scope, key, bidder, item_id
+                );
+                if let Err(err) = redis_client
+                    .increment_hash_counter(&format!("rate_limit_hits:{}", scope), key)
+                    .await
+                {
+                    eprintln!("Failed to count rate limit hit: {:?}", err);
+                }
+                return Some(retry_after_ms.div_ceil(1000).max(1));
+            }
+            Err(err) => {
+                eprintln!("Failed to check bid rate limit: {:?}", err);
+            }
+        }
+    }
+
+    None
+}
+
+pub(super) async fn count_bids(bids_collection: &Collection<Bid>, item_id: &str) -> u64 {
+    match bids_collection
+        .count_documents(doc! {"item_id": item_id}, None)
+        .await
+    {
+        Ok(count) => count,
+        Err(err) => {
+            eprintln!(
+                "Failed to count bids for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            0
+        }
+    }
+}
+
+/// The event announcing `bid`. Its sequence is filled in by the event log when
+/// the bid is swapped in.
+pub(super) fn bid_event<'a>(
+    kind: BidEventKind,
+    item_id: &'a str,
+    bid: &CurrentBid,
+    bid_count: u64,
+    timestamp: DateTime,
+    auction_end: &'a str,
+) -> BidEvent<'a> {
+    BidEvent {
+        version: BID_EVENT_VERSION,
+        kind,
+        item_id,
+        price: bid.bid_price.to_string(),
+        currency: bid.bid_price.currency,
+        bidder_alias: mask_bidder(&bid.bidder),
+        bid_count,
+        sequence: 0,
+        server_timestamp: timestamp.to_chrono().to_rfc3339(),
+        auction_end,
+    }
+}
+
+/// Puts `standing` back as the leading bid in place of `retracted`. Subscribers
+/// have already seen `retracted`, so the change is announced as a retraction.
+pub(super) async fn retract_bid(
+    redis_client: &RedisClient,
+    item: &Item,
+    retracted: &CurrentBid,
+    standing: Option<&CurrentBid>,
+    bid_count: u64,
+    auction_end: &str,
+) -> Result<(), RedisClientError> {
+    let item_id = item.id.as_deref().unwrap_or_default();
+    let starting_price = CurrentBid {
+        bid_price: item.base_price,
+        bidder: String::new(),
+    };
+    let mut event = bid_event(
+        BidEventKind::RETRACTED,
+        item_id,
+        standing.unwrap_or(&starting_price),
+        bid_count,
+        DateTime::now(),
+        auction_end,
+    );
+    if standing.is_none() {
+        event.bidder_alias = String::new();
+    }
+    redis_client
+        .compare_and_swap_with_event(
+            "current_bid",
+            item_id,
+            Some(retracted),
+            standing,
+            item_id,
+            &event,
+        )
+        .await
+        .map(|_| ())
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn record_bid(
+    redis_client: &RedisClient,
+    bids_collection: &Collection<Bid>,
+    item: &Item,
+    previous_bid: Option<&CurrentBid>,
+    current_bid: &CurrentBid,
+    proxy: bool,
+    signature: Option<BidSignature>,
+    auction_end: &str,
+) -> Result<Bid, BidPlacementError> {
+    let item_id = item.id.as_deref().unwrap_or_default();
+    let timestamp = DateTime::from_chrono(Utc::now());
+    let bid_count = count_bids(bids_collection, item_id).await;
+
+    // The bid's event is published as part of the swap, so event sequences
+    // follow the order in which bids took the lead.
+    match redis_client
+        .compare_and_swap_with_event(
+            "current_bid",
+            item_id,
+            previous_bid,
+            Some(current_bid),
+            item_id,
+            &bid_event(
+                BidEventKind::BID,
+                item_id,
+                current_bid,
+                bid_count + 1,
+                timestamp,
+                auction_end,
+            ),
+        )
+        .await
+    {
+        Ok(Some(_)) => {}
+        Ok(None) => return Err(BidPlacementError::Outbid),
+        Err(_) => return Err(BidPlacementError::CacheError),
+    }
+
+    let bid = Bid {
+        id: Some(ObjectId::new()),
+        item_id: item_id.to_string(),
+        bidder: current_bid.bidder.clone(),
+        bid_price: current_bid.bid_price,
+        timestamp,
+        proxy,
+        buy_now: false,
+        quantity: None,
+        signature,
+    };
+
+    if bids_collection.insert_one(&bid, None).await.is_err() {
+        if let Err(err) = retract_bid(
+            redis_client,
+            item,
+            current_bid,
+            previous_bid,
+            bid_count,
+            auction_end,
+        )
+        .await
+        {
+            eprintln!(
+                "Failed to roll back current bid for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+        }
+        return Err(BidPlacementError::DatabaseError);
+    }
+
+    queue_outbid_notification(redis_client, item_id, previous_bid, current_bid).await;
+
+    Ok(bid)
+}
+
+/// A bidder taking part in proxy resolution, with the highest price their
+/// maximum bid lets them reach.
+struct ProxyContender {
+    bidder: String,
+    cap: Money,
+    increment: Money,
+    signature: Option<BidSignature>,
+}
+
+impl ProxyContender {
+    fn from_proxy(proxy: ProxyBid, signed_bid_config: &SignedBidConfig) -> Self {
+        // Proxies set without a signature stop bidding once the price needs one.
+        let cap = match (
+            &proxy.signature,
+            signed_bid_config.unsigned_limit(proxy.max_bid.currency),
+        ) {
+            (None, Some(limit)) if limit < proxy.max_bid => limit,
+            _ => proxy.max_bid,
+        };
+        ProxyContender {
+            bidder: proxy.bidder,
+            cap,
+            increment: proxy.increment,
+            signature: proxy.signature,
+        }
+    }
+}
+
+/// Settles the contest between maximum bids in one step instead of raising the
+/// price one increment at a time: the runner-up is taken to their maximum and
+/// the winner to one increment above it, capped at the winner's own maximum.
+/// At most one bid is recorded per side.
+async fn run_proxy_bidding(
+    redis_client: &RedisClient,
+    db: &Database,
+    bid_increment_rules: &BidIncrementRules,
+    signed_bid_config: &SignedBidConfig,
+    item: &Item,
+    item_id: &str,
+    auction_end: &str,
+) -> Result<(), BidPlacementError> {
+    let bids_collection = db.collection::<Bid>("bids");
+    let proxy_bids_collection = db.collection::<ProxyBid>("proxy_bids");
+    let mut conflicts = 0;
+
+    loop {
+        let leading_bid: CurrentBid = match redis_client.get_value("current_bid", item_id).await {
+            Ok(Some(bid)) => bid,
+            Ok(None) => return Ok(()),
+            Err(_) => return Err(BidPlacementError::CacheError),
+        };
+
+        let find_options = FindOptions::builder()
+            .sort(doc! {"max_bid.minor_units": -1, "created_at": 1})
+            .build();
+        let proxies: Vec<ProxyBid> = match proxy_bids_collection
+            .find(doc! {"item_id": item_id}, find_options)
+            .await
+        {
+            Ok(cursor) => cursor
+                .try_collect()
+                .await
+                .map_err(|_| BidPlacementError::DatabaseError)?,
+            Err(_) => return Err(BidPlacementError::DatabaseError),
+        };
+
+        let (leader_proxies, mut challengers): (Vec<ProxyContender>, Vec<ProxyContender>) = proxies
+            .into_iter()
+            .map(|proxy| ProxyContender::from_proxy(proxy, signed_bid_config))
+            .partition(|contender| contender.bidder == leading_bid.bidder);
+        // Stable, so earlier maximum bids keep precedence on equal caps.
+        challengers.sort_by_key(|contender| std::cmp::Reverse(contender.cap.minor_units));
+
+        let leader = match leader_proxies.into_iter().next() {
+            Some(proxy) if proxy.cap > leading_bid.bid_price => proxy,
+            Some(proxy) => ProxyContender {
+                cap: leading_bid.bid_price,
+                ..proxy
+            },
+            None => ProxyContender {
+                bidder: leading_bid.bidder.clone(),
+                cap: leading_bid.bid_price,
+                increment: Money::zero(leading_bid.bid_price.currency),
+                signature: None,
+            },
+        };
+
+        let mut challengers = challengers.into_iter();
+        let best_challenger = match challengers.next() {
+            Some(challenger) => challenger,
+            None => return Ok(()),
+        };
+
+        let min_increment =
+            bid_increment_rules.min_increment(&item.category, leading_bid.bid_price);
+        let opening_price = match leading_bid
+            .bid_price
+            .checked_add(best_challenger.increment.max(min_increment))
+        {
+            Some(price) => price,
+            None => return Ok(()),
+        };
+        if best_challenger.cap < opening_price {
+            return Ok(());
+        }
+
+        // The leader keeps the lead on equal maximums, having got there first.
+        let (winner, runner_up) = if best_challenger.cap > leader.cap {
+            let runner_up = match challengers.next() {
+                Some(second) if second.cap > leader.cap => second,
+                _ => leader,
+            };
+            (best_challenger, runner_up)
+        } else {
+            (leader, best_challenger)
+        };
+
+        let step = winner
+            .increment
+            .max(bid_increment_rules.min_increment(&item.category, runner_up.cap));
+        let final_price = match runner_up.cap.checked_add(step) {
+            Some(price) if price < winner.cap => price,
+            _ => winner.cap,
+        };
+
+        // The runner-up's bid at their maximum, when it is a valid bid that the
+        // winner can still beat. The leader's own standing bid needs no new one.
+        let mut previous_bid = leading_bid;
+        let runner_up_bids = runner_up.cap < final_price
+            && if runner_up.bidder == previous_bid.bidder {
+                runner_up.cap > previous_bid.bid_price
+            } else {
+                runner_up.cap >= opening_price
+            };
+        let mut sides = vec![];
+        if runner_up_bids {
+            let bid_price = runner_up.cap;
+            sides.push((runner_up, bid_price));
+        }
+        sides.push((winner, final_price));
+
+        let mut conflicted = false;
+        for (contender, bid_price) in sides {
+            if bid_price <= previous_bid.bid_price {
+                continue;
+            }
+            let automatic_bid = CurrentBid {
+                bid_price,
+                bidder: contender.bidder,
+            };
+            match record_bid(
+                redis_client,
+                &bids_collection,
+                item,
+                Some(&previous_bid),
+                &automatic_bid,
+                true,
+                contender.signature,
+                auction_end,
+            )
+            .await
+            {
+                Ok(_) => previous_bid = automatic_bid,
+                Err(BidPlacementError::Outbid) if conflicts < MAX_PROXY_CONFLICT_RETRIES => {
+                    conflicts += 1;
+                    conflicted = true;
+                    break;
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        if !conflicted {
+            return Ok(());
+        }
+    }
+}
+
+async fn extend_auction(
+    db: &Database,
+    redis_client: &RedisClient,
+    transfer_scheduler_uri: &TransferSchedulerURI,
+    item_id: &str,
+    auction_end: chrono::DateTime<Utc>,
+) {
+    if let Err(err) = db
+        .collection::<Item>("items")
+        .update_one(
+            doc! {"_id": item_id},
+            doc! {"$max": {"auction_end": DateTime::from_chrono(auction_end)}},
+            None,
+        )
+        .await
+    {
+        eprintln!(
+            "Failed to extend auction end for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+        return;
+    }
+
+    if let Err(err) = redis_client
+        .delete_key(&format!("item_details:{}", item_id))
+        .await
+    {
+        eprintln!(
+            "Failed to delete item details for item id: {}. Error : {:?}",
+            item_id, err
+        );
+    }
+
+    let delay_in_ms = (auction_end - Utc::now()).num_milliseconds();
+    if let Err(e) = reqwest::Client::new()
+        .post(format!(
+            "{}/reschedule",
+            transfer_scheduler_uri.uri.trim_end_matches('/')
+        ))
+        .json(&json!({
+            "item_id": item_id,
+            "delay": delay_in_ms,
+        }))
+        .send()
+        .await
+    {
+        eprintln!("Failed to reschedule auction close: {:?}", e);
+    }
+}
+
+async fn withdraw_buy_now(db: &Database, redis_client: &RedisClient, item_id: &str) {
+    if let Err(err) = db
+        .collection::<Item>("items")
+        .update_one(
+            doc! {"_id": item_id},
+            doc! {"$unset": {"buy_now_price": ""}},
+            None,
+        )
+        .await
+    {
+        eprintln!(
+            "Failed to withdraw buy now price for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+        return;
+    }
+
+    if let Err(err) = redis_client
+        .delete_key(&format!("item_details:{}", item_id))
+        .await
+    {
+        eprintln!(
+            "Failed to delete item details for item id: {}. Error : {:?}",
+            item_id, err
+        );
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn accept_dutch_price(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    elasticsearch_client: &ElasticSearchClient,
+    transfer_scheduler_uri: &TransferSchedulerURI,
+    receipt_signer: &ReceiptSigner,
+    item: &Item,
+    bidder: String,
+    signature: Option<BidSignature>,
+) -> HttpResponse {
+    let item_id = item.id.clone().unwrap_or_default();
+    let now = Utc::now();
+
+    let dutch_price = match item.dutch_price(now) {
+        Some(price) => price,
+        None => {
+            return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Dutch auction schedule is missing".to_string(),
+            });
+        }
+    };
+    if exceeds_authorization(signature.as_ref(), dutch_price) {
+        return authorization_exceeded_response(signature.as_ref());
+    }
+
+    match redis_client
+        .get_value::<CurrentBid>("current_bid", &item_id)
+        .await
+    {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Item has already been bought".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Failed to retrieve current bid".to_string(),
+            });
+        }
+    }
+
+    let bid = Bid {
+        id: Some(ObjectId::new()),
+        item_id,
+        bidder,
+        bid_price: dutch_price,
+        timestamp: DateTime::from_chrono(now),
+        proxy: false,
+        buy_now: false,
+        quantity: None,
+        signature,
+    };
+    let receipt = BidReceipt::from_bid(&bid);
+
+    match close_auction_with_winner(
+        mongo_client,
+        redis_client,
+        elasticsearch_client,
+        transfer_scheduler_uri,
+        item,
+        None,
+        bid,
+    )
+    .await
+    {
+        Ok(()) => HttpResponse::Ok().json(AcceptedBidResponse {
+            status: "success".to_string(),
+            message: format!(
+                "Item bought at {}, it will shortly be available for transfer",
+                dutch_price
+            ),
+            receipt: receipt.map(|receipt| receipt_signer.sign(receipt)),
+        }),
+        Err(AuctionCloseError::Conflict) => HttpResponse::Conflict().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Item has already been bought".to_string(),
+        }),
+        Err(AuctionCloseError::NotActive) => HttpResponse::BadRequest().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Item is not available for bidding".to_string(),
+        }),
+        Err(AuctionCloseError::CacheError) => {
+            HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Failed to store bid in cache".to_string(),
+            })
+        }
+        Err(AuctionCloseError::DatabaseError) => {
+            HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Failed to record bid".to_string(),
+            })
+        }
+        Err(AuctionCloseError::TransferError) => {
+            HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Failed to register item for transfer".to_string(),
+            })
+        }
+    }
+}
+
+async fn place_sealed_bid(
+    db: &Database,
+    receipt_signer: &ReceiptSigner,
+    item: &Item,
+    bidder: &str,
+    bid_price: Option<Money>,
+    signature: Option<BidSignature>,
+) -> HttpResponse {
+    let bid_price = match bid_price {
+        Some(price) if price >= item.base_price => price,
+        _ => {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: format!(
+                    "Sealed bids need a bid_price of at least {}",
+                    item.base_price
+                ),
+            });
+        }
+    };
+    if exceeds_authorization(signature.as_ref(), bid_price) {
+        return authorization_exceeded_response(signature.as_ref());
+    }
+
+    let bid = Bid {
+        id: Some(ObjectId::new()),
+        item_id: item.id.clone().unwrap_or_default(),
+        bidder: bidder.to_string(),
+        bid_price,
+        timestamp: DateTime::from_chrono(Utc::now()),
+        proxy: false,
+        buy_now: false,
+        quantity: None,
+        signature,
+    };
+
+    // Sealed bids go straight to the bids collection: nothing is cached as the
+    // current bid or published, so the price stays hidden until the close.
+    let update_options = UpdateOptions::builder().upsert(true).build();
+    match db
+        .collection::<Bid>("bids")
+        .update_one(
+            doc! {"item_id": &bid.item_id, "bidder": bidder},
+            doc! {"$setOnInsert": {
+                "_id": bid.id,
+                "bid_price": bson::to_bson(&bid.bid_price).unwrap_or_default(),
+                "timestamp": bid.timestamp,
+                "proxy": false,
+                "buy_now": false,
+                "signature": bson::to_bson(&bid.signature).unwrap_or_default(),
+            }},
+            update_options,
+        )
+        .await
+    {
+        Ok(result) if result.upserted_id.is_some() => accepted_bid_response(
+            receipt_signer,
+            Some(&bid),
+            "Sealed bid recorded".to_string(),
+        ),
+        Ok(_) => HttpResponse::Conflict().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "A sealed bid has already been placed for this item".to_string(),
+        }),
+        Err(_) => HttpResponse::InternalServerError().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Failed to record bid".to_string(),
+        }),
+    }
+}
+
+async fn ranked_lot_bids(
+    lot_bids_collection: &Collection<LotBid>,
+    item_id: &str,
+) -> Result<Vec<LotBid>, mongodb::error::Error> {
+    let find_options = FindOptions::builder()
+        .sort(doc! {"bid_price.minor_units": -1, "updated_at": 1})
+        .build();
+    lot_bids_collection
+        .find(doc! {"item_id": item_id}, find_options)
+        .await?
+        .try_collect()
+        .await
+}
+
+#[allow(clippy::too_many_arguments)]
+async fn place_lot_bid(
+    db: &Database,
+    receipt_signer: &ReceiptSigner,
+    redis_client: &RedisClient,
+    bid_increment_rules: &BidIncrementRules,
+    item: &Item,
+    bidder: &str,
+    bid_price: Option<Money>,
+    quantity: Option<u32>,
+    signature: Option<BidSignature>,
+) -> HttpResponse {
+    let item_id = item.id.clone().unwrap_or_default();
+
+    let quantity = match quantity {
+        Some(quantity) if (1..=item.quantity).contains(&quantity) => quantity,
+        _ => {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: format!("Quantity must be between 1 and {}", item.quantity),
+            });
+        }
+    };
+
+    let lot_bids_collection = db.collection::<LotBid>("lot_bids");
+    let (own_bid, competing_bids): (Vec<LotBid>, Vec<LotBid>) =
+        match ranked_lot_bids(&lot_bids_collection, &item_id).await {
+            Ok(bids) => bids.into_iter().partition(|bid| bid.bidder == bidder),
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Database error".to_string(),
+                });
+            }
+        };
+
+    // A bid has to be able to take units from the current marginal winner, so
+    // once other bidders fill the lot it must beat the clearing price.
+    let competition = LotAllocation::from_ranked_bids(item.quantity, &competing_bids);
+    let minimum_price = match competition.clearing_price {
+        Some(clearing_price) if item.quantity - competition.units_allocated < quantity => {
+            bid_increment_rules.next_bid_price(
+                &item.category,
+                Some(clearing_price),
+                item.base_price,
+            )
+        }
+        _ => item.base_price,
+    };
+    let minimum_price = own_bid
+        .first()
+        .map_or(minimum_price, |bid| minimum_price.max(bid.bid_price));
+
+    let bid_price = match bid_price {
+        Some(price) if price >= minimum_price => price,
+        _ => {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: format!("Bid price must be at least {}", minimum_price),
+            });
+        }
+    };
+    if exceeds_authorization(signature.as_ref(), bid_price) {
+        return authorization_exceeded_response(signature.as_ref());
+    }
+
+    let now = DateTime::from_chrono(Utc::now());
+    let update_options = UpdateOptions::builder().upsert(true).build();
+    if lot_bids_collection
+        .update_one(
+            doc! {"item_id": &item_id, "bidder": bidder},
+            doc! {
+                "$set": {
+                    "quantity": quantity,
+                    "bid_price": bson::to_bson(&bid_price).unwrap_or_default(),
+                    "updated_at": now,
+                },
+                "$setOnInsert": {"created_at": now},
+            },
+            update_options,
+        )
+        .await
+        .is_err()
+    {
+        return HttpResponse::InternalServerError().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Failed to record bid".to_string(),
+        });
+    }
+
+    let bid = Bid {
+        id: Some(ObjectId::new()),
+        item_id: item_id.clone(),
+        bidder: bidder.to_string(),
+        bid_price,
+        timestamp: now,
+        proxy: false,
+        buy_now: false,
+        quantity: Some(quantity),
+        signature,
+    };
+    let recorded_bid = match db.collection::<Bid>("bids").insert_one(&bid, None).await {
+        Ok(_) => Some(bid),
+        Err(err) => {
+            eprintln!(
+                "Failed to record lot bid history for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            None
+        }
+    };
+
+    let allocation = match ranked_lot_bids(&lot_bids_collection, &item_id).await {
+        Ok(bids) => LotAllocation::from_ranked_bids(item.quantity, &bids),
+        Err(_) => LotAllocation::default(),
+    };
+
+    if let (Some(clearing_price), Some((marginal_bidder, _))) =
+        (allocation.clearing_price, allocation.winners.last())
+    {
+        let current_bid = CurrentBid {
+            bid_price: clearing_price,
+            bidder: marginal_bidder.clone(),
+        };
+        let auction_end = item.auction_end.to_chrono().to_rfc3339();
+        let bid_count = count_bids(&db.collection::<Bid>("bids"), &item_id).await;
+        if let Err(err) = redis_client
+            .set_value_with_event(
+                "current_bid",
+                &item_id,
+                &current_bid,
+                &item_id,
+                &bid_event(
+                    BidEventKind::BID,
+                    &item_id,
+                    &current_bid,
+                    bid_count,
+                    now,
+                    &auction_end,
+                ),
+            )
+            .await
+        {
+            eprintln!(
+                "Failed to cache clearing price for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+        }
+    }
+
+    let units_won = allocation.units_for(bidder);
+    accepted_bid_response(
+        receipt_signer,
+        recorded_bid.as_ref(),
+        if units_won == quantity {
+            format!("Bid placed successfully for {} units", quantity)
+        } else {
+            format!(
+                "Bid placed successfully, currently winning {} of {} units",
+                units_won, quantity
+            )
+        },
+    )
+}
+
 #[post("/api/v1/place")]
+#[allow(clippy::too_many_arguments)]
 pub async fn place_bid_handler(
+    principal: Principal,
     web::Json(payload): web::Json<PlaceBidRequest>,
     mongo_client: web::Data<MongoClient>,
     redis_client: web::Data<RedisClient>,
+    elasticsearch_client: web::Data<ElasticSearchClient>,
+    transfer_scheduler_uri: web::Data<TransferSchedulerURI>,
+    bidding_config: web::Data<BiddingConfig>,
+    receipt_signer: web::Data<ReceiptSigner>,
 ) -> impl Responder {
-    if payload.incrementation <= 0.0 {
-        return HttpResponse::BadRequest().json(PlaceBidResponse {
-            status: "error".to_string(),
-            message: "Invalid incrementation to bid price".to_string(),
-        });
+    let bidder_id = match principal.resolve(payload.bidder.as_deref()) {
+        Ok(bidder_id) => bidder_id,
+        Err(err) => return err.error_response(),
+    };
+
+    match mongo_client.is_banned_from_bidding(&bidder_id).await {
+        Ok(false) => {}
+        Ok(true) => {
+            return HttpResponse::Forbidden().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "You are banned from bidding".to_string(),
+            });
+        }
+        Err(_) => {
+            return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Database error".to_string(),
+            });
+        }
+    }
+
+    if let Some(retry_after) = check_bid_rate_limit(
+        &redis_client,
+        &bidding_config.rate_limit,
+        &bidder_id,
+        &payload.item_id,
+    )
+    .await
+    {
+        return HttpResponse::TooManyRequests()
+            .insert_header(("Retry-After", retry_after.to_string()))
+            .json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Too many bids, please slow down".to_string(),
+            });
     }
 
     let db = mongo_client.get_db();
     let items_collection = db.collection::<Item>("items");
     let bids_collection = db.collection::<Bid>("bids");
-    let bidder_id = payload.bidder;
 
     let item = match items_collection
         .find_one(doc! {"_id": &payload.item_id}, None)
         .await
     {
-        Ok(Some(item)) if item.status.to_string() == "ACTIVE" => item,
+        Ok(Some(item)) if matches!(item.status, ItemStatus::ACTIVE | ItemStatus::SCHEDULED) => item,
         Ok(Some(_)) => {
             return HttpResponse::BadRequest().json(PlaceBidResponse {
                 status: "error".to_string(),
@@ -62,24 +1128,169 @@ pub async fn place_bid_handler(
         }
     };
 
-    let is_initial_bid = match bids_collection
-        .find_one(doc! {"item_id" : &payload.item_id}, None)
-        .await
-    {
-        Ok(Some(_bid)) => false,
-        Ok(None) => true,
-        Err(_) => {
-            return HttpResponse::InternalServerError().json(PlaceBidResponse {
+    let is_seller = match &item.seller {
+        Some(_) => item.is_seller(&bidder_id),
+        None => match mongo_client.find_item_seller(&payload.item_id).await {
+            Ok(seller) => seller.is_some_and(|seller| seller.eq_ignore_ascii_case(&bidder_id)),
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Database error".to_string(),
+                });
+            }
+        },
+    };
+    if is_seller {
+        return HttpResponse::Forbidden().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Sellers cannot bid on their own items".to_string(),
+        });
+    }
+
+    let now = Utc::now();
+    if item.auction_end.to_chrono() <= now {
+        return HttpResponse::BadRequest().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Auction has ended".to_string(),
+        });
+    }
+    if !item.is_open(now) {
+        return HttpResponse::BadRequest().json(PlaceBidResponse {
+            status: "error".to_string(),
+            message: "Auction has not started yet".to_string(),
+        });
+    }
+
+    let currency = item.currency();
+    let (incrementation, max_bid, bid_price) = match (
+        PriceInput::to_optional_money(payload.incrementation.as_ref(), currency),
+        PriceInput::to_optional_money(payload.max_bid.as_ref(), currency),
+        PriceInput::to_optional_money(payload.bid_price.as_ref(), currency),
+    ) {
+        (Some(incrementation), Some(max_bid), Some(bid_price)) => {
+            (incrementation, max_bid, bid_price)
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
                 status: "error".to_string(),
-                message: "Database error".to_string(),
+                message: format!("Bid amounts must be positive amounts in {}", currency),
             });
         }
     };
 
-    let bid_price = if is_initial_bid {
-        item.base_price
-    } else {
-        let current_bid: Option<CurrentBid> = match redis_client
+    let signature = match &payload.authorization {
+        Some(authorization) => match verify_bid_authorization(
+            &redis_client,
+            &bidding_config.signed_bids,
+            &item,
+            &bidder_id,
+            authorization,
+        )
+        .await
+        {
+            Ok(signature) => Some(signature),
+            Err(err) => return err.error_response(),
+        },
+        None => None,
+    };
+
+    // The authorization's nonce is only spent once the bid is accepted.
+    let reserved_nonce = signature.clone();
+    let response = async {
+        // An item is high value once its starting, leading or requested price
+        // reaches the threshold, and from then on every bid must be signed.
+        if signature.is_none() {
+            let leading_bid_price = match redis_client
+                .get_value::<CurrentBid>("current_bid", &payload.item_id)
+                .await
+            {
+                Ok(bid) => bid.map(|bid| bid.bid_price),
+                Err(_) => {
+                    return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                        status: "error".to_string(),
+                        message: "Failed to retrieve current bid".to_string(),
+                    });
+                }
+            };
+            let is_high_value = [Some(item.base_price), leading_bid_price, bid_price, max_bid]
+                .into_iter()
+                .flatten()
+                .any(|price| bidding_config.signed_bids.is_required(price));
+            if is_high_value {
+                return signature_required_response();
+            }
+        }
+
+        if item.auction_type == AuctionType::DUTCH {
+            return accept_dutch_price(
+                &mongo_client,
+                &redis_client,
+                &elasticsearch_client,
+                &transfer_scheduler_uri,
+                &receipt_signer,
+                &item,
+                bidder_id,
+                signature,
+            )
+            .await;
+        }
+
+        if item.auction_type.is_sealed() {
+            return place_sealed_bid(
+                &db,
+                &receipt_signer,
+                &item,
+                &bidder_id,
+                bid_price,
+                signature,
+            )
+            .await;
+        }
+
+        if item.is_lot() {
+            return place_lot_bid(
+                &db,
+                &receipt_signer,
+                &redis_client,
+                &bidding_config.increments,
+                &item,
+                &bidder_id,
+                bid_price,
+                payload.quantity,
+                signature,
+            )
+            .await;
+        }
+
+        if incrementation.is_none() && max_bid.is_none() {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: "Invalid incrementation to bid price".to_string(),
+            });
+        }
+
+        let extended_auction_end = bidding_config
+            .soft_close
+            .extended_auction_end(item.auction_end.to_chrono(), now);
+        let auction_end = extended_auction_end
+            .unwrap_or_else(|| item.auction_end.to_chrono())
+            .to_rfc3339();
+
+        let is_initial_bid = match bids_collection
+            .find_one(doc! {"item_id" : &payload.item_id}, None)
+            .await
+        {
+            Ok(Some(_bid)) => false,
+            Ok(None) => true,
+            Err(_) => {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Database error".to_string(),
+                });
+            }
+        };
+
+        let previous_bid: Option<CurrentBid> = match redis_client
             .get_value("current_bid", &payload.item_id)
             .await
         {
@@ -92,55 +1303,218 @@ pub async fn place_bid_handler(
             }
         };
 
-        match current_bid {
-            Some(bid) => bid.bid_price + payload.incrementation,
-            None => {
+        // A buy now win becomes the leading bid a moment before the item is
+        // marked TRANSFERRING, and must not be outbid in between.
+        if let (Some(buy_now_price), Some(bid)) = (item.buy_now_price, &previous_bid) {
+            if bid.bid_price >= buy_now_price {
+                return HttpResponse::BadRequest().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: format!(
+                        "The leading bid has reached the buy now price of {}",
+                        buy_now_price
+                    ),
+                });
+            }
+        }
+
+        let min_increment = previous_bid.as_ref().map_or(Money::zero(currency), |bid| {
+            bidding_config
+                .increments
+                .min_increment(&item.category, bid.bid_price)
+        });
+        let incrementation = incrementation.unwrap_or(min_increment);
+        if incrementation < min_increment {
+            return HttpResponse::BadRequest().json(PlaceBidResponse {
+                status: "error".to_string(),
+                message: format!("Bid increment must be at least {}", min_increment),
+            });
+        }
+
+        let bid_price = match (&previous_bid, is_initial_bid) {
+            (Some(bid), _) => match bid.bid_price.checked_add(incrementation) {
+                Some(bid_price) => bid_price,
+                None => {
+                    return HttpResponse::BadRequest().json(PlaceBidResponse {
+                        status: "error".to_string(),
+                        message: "Invalid incrementation to bid price".to_string(),
+                    })
+                }
+            },
+            (None, true) => item.base_price,
+            (None, false) => {
                 return HttpResponse::BadRequest().json(PlaceBidResponse {
                     status: "error".to_string(),
                     message: "Bids not found despite not being initial bid".to_string(),
                 })
             }
+        };
+
+        // A minimum increment bid only learns its price here, which may be the
+        // one that crosses the threshold.
+        if signature.is_none() && bidding_config.signed_bids.is_required(bid_price) {
+            return signature_required_response();
         }
-    };
 
-    let current_bid = CurrentBid {
-        bid_price,
-        bidder: bidder_id.clone(),
-    };
+        if exceeds_authorization(signature.as_ref(), bid_price)
+            || max_bid.is_some_and(|max_bid| exceeds_authorization(signature.as_ref(), max_bid))
+        {
+            return authorization_exceeded_response(signature.as_ref());
+        }
+
+        let is_leading = previous_bid
+            .as_ref()
+            .is_some_and(|bid| bid.bidder == bidder_id);
 
-    if let Err(_) = redis_client
-        .set_value("current_bid", &payload.item_id, &current_bid, None)
+        if let Some(max_bid) = max_bid {
+            let minimum_max_bid = if is_leading {
+                previous_bid.as_ref().map_or(bid_price, |bid| bid.bid_price)
+            } else {
+                bid_price
+            };
+            if max_bid < minimum_max_bid {
+                return HttpResponse::BadRequest().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: format!("Maximum bid must be at least {}", minimum_max_bid),
+                });
+            }
+
+            let now = DateTime::from_chrono(Utc::now());
+            let update_options = UpdateOptions::builder().upsert(true).build();
+            if db
+                .collection::<ProxyBid>("proxy_bids")
+                .update_one(
+                    doc! {"item_id": &payload.item_id, "bidder": &bidder_id},
+                    doc! {
+                        "$set": {
+                            "max_bid": bson::to_bson(&max_bid).unwrap_or_default(),
+                            "increment": bson::to_bson(&incrementation).unwrap_or_default(),
+                            "signature": bson::to_bson(&signature).unwrap_or_default(),
+                            "updated_at": now,
+                        },
+                        "$setOnInsert": {"created_at": now},
+                    },
+                    update_options,
+                )
+                .await
+                .is_err()
+            {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Failed to record maximum bid".to_string(),
+                });
+            }
+
+            // Raising a maximum while leading places no bid, so there is nothing
+            // for a receipt to vouch for yet. Bids the maximum places later on are
+            // recorded in the bid history like any other.
+            if is_leading {
+                return accepted_bid_response(
+                    &receipt_signer,
+                    None,
+                    "Maximum bid updated".to_string(),
+                );
+            }
+        }
+
+        let current_bid = CurrentBid {
+            bid_price,
+            bidder: bidder_id.clone(),
+        };
+
+        let bid = match record_bid(
+            &redis_client,
+            &bids_collection,
+            &item,
+            previous_bid.as_ref(),
+            &current_bid,
+            max_bid.is_some(),
+            signature,
+            &auction_end,
+        )
         .await
-    {
-        return HttpResponse::InternalServerError().json(PlaceBidResponse {
-            status: "error".to_string(),
-            message: "Failed to store bid in cache".to_string(),
-        });
-    }
+        {
+            Ok(bid) => bid,
+            Err(BidPlacementError::Outbid) => {
+                return HttpResponse::Conflict().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Outbid by a concurrent bid, please retry".to_string(),
+                });
+            }
+            Err(BidPlacementError::CacheError) => {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Failed to store bid in cache".to_string(),
+                });
+            }
+            Err(BidPlacementError::DatabaseError) => {
+                return HttpResponse::InternalServerError().json(PlaceBidResponse {
+                    status: "error".to_string(),
+                    message: "Failed to record bid".to_string(),
+                });
+            }
+        };
 
-    let bid = Bid {
-        id: None,
-        item_id: payload.item_id.clone(),
-        bidder: bidder_id.clone(),
-        bid_price,
-        timestamp: DateTime::from_chrono(Utc::now()),
-    };
+        if let Some(extended_auction_end) = extended_auction_end {
+            extend_auction(
+                &db,
+                &redis_client,
+                &transfer_scheduler_uri,
+                &payload.item_id,
+                extended_auction_end,
+            )
+            .await;
+        }
 
-    if let Err(_) = bids_collection.insert_one(bid, None).await {
-        return HttpResponse::InternalServerError().json(PlaceBidResponse {
-            status: "error".to_string(),
-            message: "Failed to record bid".to_string(),
-        });
-    }
-    let bid_price_str = bid_price.to_string();
-    let message = MessageToPublish::new(&bid_price_str);
+        if let Err(err) = run_proxy_bidding(
+            &redis_client,
+            &db,
+            &bidding_config.increments,
+            &bidding_config.signed_bids,
+            &item,
+            &payload.item_id,
+            &auction_end,
+        )
+        .await
+        {
+            eprintln!(
+                "Failed to run proxy bidding for item_id: {}. Error: {:?}",
+                payload.item_id, err
+            );
+        }
+
+        let leading_bid: Option<CurrentBid> = redis_client
+            .get_value("current_bid", &payload.item_id)
+            .await
+            .unwrap_or_default();
+
+        if let Some(buy_now_price) = item.buy_now_price {
+            let leading_bid_price = leading_bid.as_ref().map(|bid| bid.bid_price);
+            if !bidding_config
+                .buy_now
+                .is_available(buy_now_price, leading_bid_price)
+            {
+                withdraw_buy_now(&db, &redis_client, &payload.item_id).await;
+            }
+        }
+
+        let still_leading = leading_bid.is_some_and(|bid| bid.bidder == bidder_id);
 
-    if let Err(_) = redis_client.publish(message, &payload.item_id).await {
-        eprintln!("Failed to publish bid update");
+        accepted_bid_response(
+            &receipt_signer,
+            Some(&bid),
+            if still_leading {
+                "Bid placed successfully".to_string()
+            } else {
+                "Bid placed successfully, but outbid by an existing maximum bid".to_string()
+            },
+        )
     }
+    .await;
 
-    HttpResponse::Ok().json(PlaceBidResponse {
-        status: "success".to_string(),
-        message: "Bid placed successfully".to_string(),
-    })
+    if let Some(signature) = reserved_nonce {
+        if !response.status().is_success() {
+            release_bid_nonce(&redis_client, &signature).await;
+        }
+    }
+    response
 }
@@ -8,9 +8,11 @@ use std::path::Path;
 use uuid::Uuid;
 
 use crate::{
+    auth::Principal,
     awss3::AWSClient,
     elasticsearch::ElasticSearchClient,
-    mongo::{Item, ItemStatus, MongoClient},
+    money::{Currency, PriceInput},
+    mongo::{AuctionType, DutchSchedule, Item, ItemStatus, MongoClient},
     redis::RedisClient,
     types::{BlockchainAPIURI, MessageToEnqueue, TransferSchedulerURI},
 };
@@ -18,8 +20,17 @@ use crate::{
 #[derive(Deserialize, Debug)]
 pub struct CreateItemRequest {
     item_details: ItemDetails,
-    seller: String,
-    auction_end: i64,
+    seller: Option<String>,
+    auction_start: Option<chrono::DateTime<Utc>>,
+    auction_end: AuctionEnd,
+}
+
+/// `auction_end` is either an offset in seconds from now or an RFC 3339 time.
+#[derive(Deserialize, Debug)]
+#[serde(untagged)]
+enum AuctionEnd {
+    Offset(i64),
+    At(chrono::DateTime<Utc>),
 }
 
 #[derive(Deserialize, Debug)]
@@ -28,7 +39,21 @@ struct ItemDetails {
     description: String,
     images: Vec<String>,
     category: String,
-    base_price: f64,
+    base_price: PriceInput,
+    reserve_price: Option<PriceInput>,
+    buy_now_price: Option<PriceInput>,
+    currency: Option<String>,
+    #[serde(default)]
+    auction_type: AuctionType,
+    dutch_schedule: Option<DutchScheduleDetails>,
+    quantity: Option<u32>,
+}
+
+#[derive(Deserialize, Debug)]
+struct DutchScheduleDetails {
+    floor_price: PriceInput,
+    decrement: PriceInput,
+    interval_seconds: i64,
 }
 
 #[derive(Deserialize)]
@@ -145,7 +170,9 @@ async fn upload_images(
 }
 
 #[post("/api/v1/item")]
+#[allow(clippy::too_many_arguments)]
 pub async fn post_item_handler(
+    principal: Principal,
     req: web::Json<CreateItemRequest>,
     mongo_client: web::Data<MongoClient>,
     redis_client: web::Data<RedisClient>,
@@ -154,10 +181,173 @@ pub async fn post_item_handler(
     blockchain_api_base_uri: web::Data<BlockchainAPIURI>,
     transfer_scheduler_uri: web::Data<TransferSchedulerURI>,
 ) -> impl Responder {
+    let seller = match principal.resolve(req.seller.as_deref()) {
+        Ok(seller) => seller,
+        Err(err) => return err.error_response(),
+    };
     let item_id = Uuid::new_v4().to_string()[..16].to_string();
 
-    let chrono_dt = Utc::now() + Duration::seconds(req.auction_end);
-    let auction_end = DateTime::from_chrono(chrono_dt);
+    let now = Utc::now();
+    if req
+        .auction_start
+        .is_some_and(|auction_start| auction_start < now)
+    {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Auction start cannot be in the past".to_string(),
+        });
+    }
+    let auction_start = req.auction_start;
+    let auction_end = match req.auction_end {
+        AuctionEnd::Offset(seconds) => now + Duration::seconds(seconds),
+        AuctionEnd::At(auction_end) => auction_end,
+    };
+    if auction_end <= auction_start.unwrap_or(now) {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Auction end must be after the auction start".to_string(),
+        });
+    }
+
+    let currency = match &req.item_details.currency {
+        Some(code) => match Currency::parse(code) {
+            Some(currency) => currency,
+            None => {
+                return HttpResponse::BadRequest().json(CreateItemResponse {
+                    status: "error".to_string(),
+                    item_id,
+                    operation_id: None,
+                    message: "Invalid currency code".to_string(),
+                });
+            }
+        },
+        None => Currency::USD,
+    };
+
+    let parse_price = |price| PriceInput::to_optional_money(price, currency);
+    let (base_price, reserve_price, buy_now_price) = match (
+        parse_price(Some(&req.item_details.base_price)),
+        parse_price(req.item_details.reserve_price.as_ref()),
+        parse_price(req.item_details.buy_now_price.as_ref()),
+    ) {
+        (Some(Some(base_price)), Some(reserve_price), Some(buy_now_price)) => {
+            (base_price, reserve_price, buy_now_price)
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(CreateItemResponse {
+                status: "error".to_string(),
+                item_id,
+                operation_id: None,
+                message: format!("Prices must be positive amounts in {}", currency),
+            });
+        }
+    };
+
+    if reserve_price.is_some_and(|reserve_price| reserve_price < base_price) {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Reserve price cannot be lower than base price".to_string(),
+        });
+    }
+
+    if buy_now_price.is_some_and(|buy_now_price| {
+        buy_now_price <= base_price
+            || reserve_price.is_some_and(|reserve_price| buy_now_price < reserve_price)
+    }) {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Buy now price must be above base price and reserve price".to_string(),
+        });
+    }
+
+    if req.item_details.auction_type.is_sealed() && buy_now_price.is_some() {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Buy now is not available for sealed-bid auctions".to_string(),
+        });
+    }
+
+    let quantity = req.item_details.quantity.unwrap_or(1);
+    if quantity == 0 {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Quantity must be at least 1".to_string(),
+        });
+    }
+
+    if quantity > 1
+        && (req.item_details.auction_type != AuctionType::ENGLISH || buy_now_price.is_some())
+    {
+        return HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: "Multi-quantity lots must be English auctions without a buy now price"
+                .to_string(),
+        });
+    }
+
+    let dutch_schedule = match (
+        &req.item_details.auction_type,
+        &req.item_details.dutch_schedule,
+    ) {
+        (
+            AuctionType::ENGLISH
+            | AuctionType::SEALED_FIRST_PRICE
+            | AuctionType::SEALED_SECOND_PRICE,
+            None,
+        ) => None,
+        (AuctionType::DUTCH, Some(schedule))
+            if schedule.interval_seconds > 0
+                && reserve_price.is_none()
+                && buy_now_price.is_none() =>
+        {
+            match (
+                parse_price(Some(&schedule.floor_price)),
+                parse_price(Some(&schedule.decrement)),
+            ) {
+                (Some(Some(floor_price)), Some(Some(decrement))) if floor_price < base_price => {
+                    Some(DutchSchedule {
+                        start_price: base_price,
+                        floor_price,
+                        decrement,
+                        interval_seconds: schedule.interval_seconds,
+                        starts_at: DateTime::from_chrono(auction_start.unwrap_or(now)),
+                    })
+                }
+                _ => {
+                    return HttpResponse::BadRequest().json(CreateItemResponse {
+                        status: "error".to_string(),
+                        item_id,
+                        operation_id: None,
+                        message: "Invalid Dutch auction schedule".to_string(),
+                    });
+                }
+            }
+        }
+        _ => {
+            return HttpResponse::BadRequest().json(CreateItemResponse {
+                status: "error".to_string(),
+                item_id,
+                operation_id: None,
+                message: "Invalid Dutch auction schedule".to_string(),
+            });
+        }
+    };
+
+    let auction_end = DateTime::from_chrono(auction_end);
 
     let image_urls = match upload_images(&s3_client, &req.item_details.images, &item_id).await {
         Ok(urls) => urls,
@@ -173,11 +363,11 @@ pub async fn post_item_handler(
 
     let blockchain_payload = json!({
         "item_id": item_id,
-        "seller": req.seller
+        "seller": seller
     });
 
     let blockchain_response = match reqwest::Client::new()
-        .post(&format!("{}/item", blockchain_api_base_uri.uri))
+        .post(format!("{}/item", blockchain_api_base_uri.uri))
         .json(&blockchain_payload)
         .send()
         .await
@@ -204,14 +394,12 @@ pub async fn post_item_handler(
     };
 
     match blockchain_response.status.as_str() {
-        "error" => {
-            return HttpResponse::BadRequest().json(CreateItemResponse {
-                status: "error".to_string(),
-                item_id,
-                operation_id: None,
-                message: blockchain_response.message,
-            });
-        }
+        "error" => HttpResponse::BadRequest().json(CreateItemResponse {
+            status: "error".to_string(),
+            item_id,
+            operation_id: None,
+            message: blockchain_response.message,
+        }),
         "pending" => {
             let item = Item {
                 id: Some(item_id.clone()),
@@ -219,14 +407,23 @@ pub async fn post_item_handler(
                 description: req.item_details.description.clone(),
                 images: image_urls,
                 category: req.item_details.category.clone().to_lowercase(),
+                auction_start: auction_start.map(DateTime::from_chrono),
                 auction_end,
                 rating: -1.0,
                 status: ItemStatus::PENDING,
-                base_price: req.item_details.base_price,
+                base_price,
+                reserve_price,
+                buy_now_price,
+                seller: Some(seller.clone()),
+                auction_type: req.item_details.auction_type.clone(),
+                dutch_schedule,
+                clearing_price: None,
+                quantity,
+                search_alerts_pending: true,
             };
 
             let items_collection = mongo_client.get_db().collection::<Item>("items");
-            match items_collection.insert_one(item, None).await {
+            match items_collection.insert_one(&item, None).await {
                 Ok(_) => {
                     let auction_end_chrono = auction_end.to_chrono();
                     let delay_in_ms = (auction_end_chrono - Utc::now()).num_milliseconds();
@@ -237,7 +434,7 @@ pub async fn post_item_handler(
                             "item_id": item_id,
                             "item_name": req.item_details.title,
                             "delay": delay_in_ms,
-                            "seller": req.seller
+                            "seller": seller
                         }))
                         .send()
                         .await
@@ -0,0 +1,54 @@
+use actix_web::{delete, web, HttpResponse, Responder};
+use bson::doc;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    auth::Principal,
+    mongo::{MongoClient, WatchlistEntry},
+};
+
+#[derive(Deserialize)]
+struct WatchlistRequest {
+    user_id: Option<String>,
+}
+
+#[derive(Serialize)]
+struct WatchlistResponse {
+    status: String,
+    message: String,
+}
+
+#[delete("/api/v1/watchlist/{item_id}")]
+pub async fn remove_from_watchlist_handler(
+    principal: Principal,
+    item_id: web::Path<String>,
+    req_body: Option<web::Json<WatchlistRequest>>,
+    mongo_client: web::Data<MongoClient>,
+) -> impl Responder {
+    let item_id = item_id.into_inner();
+    let requested_user = req_body.and_then(|body| body.into_inner().user_id);
+    let user_id = match principal.resolve(requested_user.as_deref()) {
+        Ok(user_id) => user_id,
+        Err(err) => return err.error_response(),
+    };
+
+    match mongo_client
+        .get_db()
+        .collection::<WatchlistEntry>("watchlists")
+        .delete_one(doc! {"user_id": &user_id, "item_id": &item_id}, None)
+        .await
+    {
+        Ok(result) if result.deleted_count > 0 => HttpResponse::Ok().json(WatchlistResponse {
+            status: "success".to_string(),
+            message: "Item removed from watchlist".to_string(),
+        }),
+        Ok(_) => HttpResponse::NotFound().json(WatchlistResponse {
+            status: "error".to_string(),
+            message: "Item is not in watchlist".to_string(),
+        }),
+        Err(_) => HttpResponse::InternalServerError().json(WatchlistResponse {
+            status: "error".to_string(),
+            message: "Failed to update watchlist".to_string(),
+        }),
+    }
+}
@@ -0,0 +1,110 @@
+use bson::{doc, oid::ObjectId, DateTime};
+use chrono::Utc;
+use futures::TryStreamExt;
+
+use crate::{
+    elasticsearch::ElasticSearchClient,
+    mongo::{Item, MongoClient, SavedSearch, SearchAlert},
+    notifications::{queue_notification, Notification, NotificationKind},
+    redis::RedisClient,
+};
+
+const MAX_MATCHED_SEARCHES: usize = 1000;
+
+/// Matches a newly active item against every saved search and records an alert,
+/// plus a notification, for each active search it satisfies. Sellers are not
+/// alerted about their own items.
+pub(crate) async fn alert_saved_searches(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    elasticsearch_client: &ElasticSearchClient,
+    item: &Item,
+) {
+    let item_id = item.id.clone().unwrap_or_default();
+
+    let search_ids: Vec<ObjectId> = match elasticsearch_client
+        .match_saved_searches(&item.title, &item.category, MAX_MATCHED_SEARCHES)
+        .await
+    {
+        Ok(search_ids) => search_ids
+            .iter()
+            .filter_map(|id| ObjectId::parse_str(id).ok())
+            .collect(),
+        Err(err) => {
+            eprintln!(
+                "Failed to match saved searches for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            return;
+        }
+    };
+
+    if search_ids.is_empty() {
+        return;
+    }
+
+    let db = mongo_client.get_db();
+    let saved_searches: Vec<SavedSearch> = match db
+        .collection::<SavedSearch>("saved_searches")
+        .find(doc! {"_id": {"$in": search_ids}, "paused": false}, None)
+        .await
+    {
+        Ok(cursor) => cursor.try_collect().await.unwrap_or_default(),
+        Err(err) => {
+            eprintln!(
+                "Failed to fetch saved searches for item_id: {}. Error: {:?}",
+                item_id, err
+            );
+            return;
+        }
+    };
+
+    let now = DateTime::from_chrono(Utc::now());
+    let alerts: Vec<SearchAlert> = saved_searches
+        .iter()
+        .filter(|search| !item.is_seller(&search.user_id) && search.filters.matches(item))
+        .filter_map(|search| {
+            Some(SearchAlert {
+                id: None,
+                saved_search_id: search.id?,
+                user_id: search.user_id.clone(),
+                item_id: item_id.clone(),
+                item_title: item.title.clone(),
+                created_at: now,
+            })
+        })
+        .collect();
+
+    if alerts.is_empty() {
+        return;
+    }
+
+    for alert in &alerts {
+        let search_query = saved_searches
+            .iter()
+            .find(|search| search.id == Some(alert.saved_search_id))
+            .map(|search| search.query.as_str())
+            .unwrap_or_default();
+        let notification = Notification::new(
+            &alert.user_id,
+            NotificationKind::NEW_LISTING,
+            &item_id,
+            format!(
+                "New listing {} matches your saved search \"{}\"",
+                item.title, search_query
+            ),
+        );
+        queue_notification(redis_client, &notification).await;
+    }
+
+    if let Err(err) = db
+        .collection::<SearchAlert>("search_alerts")
+        .insert_many(alerts, None)
+        .await
+    {
+        eprintln!(
+            "Failed to record search alerts for item_id: {}. Error: {:?}",
+            item_id, err
+        );
+    }
+}
@@ -6,6 +6,7 @@ use actix_web::{
 use serde_json::json;
 use std::collections::HashMap;
 
+use super::item_view::public_items;
 use crate::{
     elasticsearch::ElasticSearchClient,
     mongo::{Item, MongoClient},
@@ -41,11 +42,10 @@ pub async fn search_item_handler(
             for result in search_results {
                 let item_id = &result.item_id;
 
-                let item_details: Option<Item> =
-                    match redis_client.get_value("item_details", item_id).await {
-                        Ok(details) => details,
-                        Err(_) => None,
-                    };
+                let item_details: Option<Item> = redis_client
+                    .get_value("item_details", item_id)
+                    .await
+                    .unwrap_or_default();
 
                 let item_details = match item_details {
                     Some(details) => details,
@@ -80,7 +80,7 @@ pub async fn search_item_handler(
 
             HttpResponse::Ok().json(json!({
                 "status": "success",
-                "results": sorted_items
+                "results": public_items(&redis_client, &sorted_items).await
             }))
         }
         Err(e) => {
@@ -3,6 +3,7 @@ use bson::{doc, Bson};
 use serde::{Deserialize, Serialize};
 
 use crate::{
+    auth::Principal,
     elasticsearch::ElasticSearchClient,
     mongo::{Item, MongoClient},
     redis::RedisClient,
@@ -12,7 +13,7 @@ use crate::{
 #[derive(Serialize, Deserialize)]
 struct TransferItemRequest {
     item_id: String,
-    buyer: String,
+    buyer: Option<String>,
 }
 
 #[derive(Serialize, Deserialize)]
@@ -45,6 +46,7 @@ struct ItemDetails {
 
 #[post("/api/v1/transfer")]
 pub async fn transfer_item_handler(
+    principal: Principal,
     req_body: web::Json<TransferItemRequest>,
     mongo_client: web::Data<MongoClient>,
     redis_client: web::Data<RedisClient>,
@@ -52,7 +54,10 @@ pub async fn transfer_item_handler(
     blockchain_api_base_uri: web::Data<BlockchainAPIURI>,
 ) -> impl Responder {
     let item_id = req_body.item_id.clone();
-    let buyer = req_body.buyer.clone();
+    let buyer = match principal.resolve(req_body.buyer.as_deref()) {
+        Ok(buyer) => buyer,
+        Err(err) => return err.error_response(),
+    };
     let blockchain_api_base_uri = &blockchain_api_base_uri.uri;
 
     let transferring_items = match redis_client
@@ -95,7 +100,7 @@ pub async fn transfer_item_handler(
     };
 
     let blockchain_response = match reqwest::Client::new()
-        .post(&format!("{}/transfer", blockchain_api_base_uri))
+        .post(format!("{}/transfer", blockchain_api_base_uri))
         .json(&transfer_req)
         .send()
         .await
@@ -133,11 +138,31 @@ pub async fn transfer_item_handler(
                 );
             }
 
+            // A lot is only SOLD once the last of its winners has claimed their
+            // units, until then it stays TRANSFERRING for the others.
+            let remaining_transfers = doc! {
+                "$subtract": [{"$ifNull": ["$pending_transfers", 1]}, 1]
+            };
             let collection = mongo_client.get_db().collection::<Item>("items");
             match collection
                 .update_one(
                     doc! {"_id": &item_id},
-                    doc! {"$set": {"status": Bson::String("SOLD".to_string())}},
+                    vec![doc! {"$set": {
+                        "status": {
+                            "$cond": [
+                                {"$gt": [&remaining_transfers, 0]},
+                                "$status",
+                                Bson::String("SOLD".to_string()),
+                            ]
+                        },
+                        "pending_transfers": {
+                            "$cond": [
+                                {"$gt": [&remaining_transfers, 0]},
+                                &remaining_transfers,
+                                "$$REMOVE",
+                            ]
+                        },
+                    }}],
                     None,
                 )
                 .await
@@ -156,21 +181,20 @@ pub async fn transfer_item_handler(
                     };
 
                     for similar_item_id in similar_items {
-                        match redis_client.get_similar_items(&similar_item_id).await {
-                            Ok(Some(mut refs)) => {
-                                refs.retain(|id| id != &item_id);
-
-                                if refs.is_empty() {
-                                    let _ = redis_client
-                                        .delete_key(&format!("similar_items:{}", similar_item_id))
-                                        .await;
-                                } else {
-                                    let _ = redis_client
-                                        .set_similar_items(&similar_item_id, &refs)
-                                        .await;
-                                }
+                        if let Ok(Some(mut refs)) =
+                            redis_client.get_similar_items(&similar_item_id).await
+                        {
+                            refs.retain(|id| id != &item_id);
+
+                            if refs.is_empty() {
+                                let _ = redis_client
+                                    .delete_key(&format!("similar_items:{}", similar_item_id))
+                                    .await;
+                            } else {
+                                let _ = redis_client
+                                    .set_similar_items(&similar_item_id, &refs)
+                                    .await;
                             }
-                            _ => {}
                         }
                     }
 
@@ -0,0 +1,78 @@
+use actix_web::{get, web, HttpResponse, Responder};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    money::{Currency, Money},
+    receipts::{BidReceipt, ReceiptSigner},
+};
+
+#[derive(Deserialize)]
+struct VerifyReceiptQuery {
+    bid_id: String,
+    item_id: String,
+    bidder: String,
+    minor_units: i64,
+    currency: String,
+    server_timestamp: String,
+    key_id: Option<String>,
+    signature: String,
+}
+
+#[derive(Serialize)]
+struct VerifyReceiptResponse {
+    status: String,
+    valid: bool,
+    message: String,
+}
+
+/// Checks a bid receipt against the server's signing key. The amount is given
+/// as `minor_units` and `currency`, the same way the receipt carries it.
+#[get("/api/v1/receipts/verify")]
+pub async fn verify_receipt_handler(
+    query: web::Query<VerifyReceiptQuery>,
+    receipt_signer: web::Data<ReceiptSigner>,
+) -> impl Responder {
+    let query = query.into_inner();
+
+    let amount = match Currency::parse(&query.currency) {
+        Some(currency) => Money::new(query.minor_units, currency),
+        None => {
+            return HttpResponse::BadRequest().json(VerifyReceiptResponse {
+                status: "error".to_string(),
+                valid: false,
+                message: "Invalid currency".to_string(),
+            });
+        }
+    };
+
+    if query
+        .key_id
+        .as_deref()
+        .is_some_and(|key_id| key_id != receipt_signer.key_id())
+    {
+        return HttpResponse::Ok().json(VerifyReceiptResponse {
+            status: "success".to_string(),
+            valid: false,
+            message: "Receipt was signed with an unknown key".to_string(),
+        });
+    }
+
+    let receipt = BidReceipt {
+        bid_id: query.bid_id,
+        item_id: query.item_id,
+        bidder: query.bidder,
+        amount,
+        server_timestamp: query.server_timestamp,
+    };
+    let valid = receipt_signer.verify(&receipt, &query.signature);
+
+    HttpResponse::Ok().json(VerifyReceiptResponse {
+        status: "success".to_string(),
+        valid,
+        message: if valid {
+            "Receipt signature is valid".to_string()
+        } else {
+            "Receipt signature does not match".to_string()
+        },
+    })
+}
@@ -0,0 +1,313 @@
+use actix_web::{
+    body::{self, BoxBody, MessageBody},
+    dev::{ServiceRequest, ServiceResponse},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    web, Error, HttpMessage, HttpResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+
+use crate::{auth::Principal, redis::RedisClient, types::IdempotencyConfig};
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+const IDEMPOTENT_REPLAYED_HEADER: &str = "Idempotent-Replayed";
+const MAX_KEY_LENGTH: usize = 255;
+// Sign-in responses carry a fresh session token, which must never be written
+// to Redis in plaintext.
+const EXCLUDED_PATH_PREFIX: &str = "/api/v1/auth/";
+
+#[derive(Serialize, Deserialize)]
+#[serde(tag = "state")]
+enum StoredResponse {
+    InProgress {
+        fingerprint: String,
+    },
+    Completed {
+        fingerprint: String,
+        status: u16,
+        content_type: Option<String>,
+        #[serde(default)]
+        headers: Vec<(String, String)>,
+        body: String,
+    },
+}
+
+impl StoredResponse {
+    fn fingerprint(&self) -> &str {
+        match self {
+            StoredResponse::InProgress { fingerprint }
+            | StoredResponse::Completed { fingerprint, .. } => fingerprint,
+        }
+    }
+}
+
+/// Whether a response is final for the request that produced it. Conflicts,
+/// rate limits and timeouts ask the client to retry, so replaying them would
+/// repeat the failure until the key expires.
+fn is_replayable(status: StatusCode) -> bool {
+    status.is_success()
+        || (status.is_client_error()
+            && !matches!(
+                status,
+                StatusCode::REQUEST_TIMEOUT | StatusCode::CONFLICT | StatusCode::TOO_MANY_REQUESTS
+            ))
+}
+
+fn error_response(status: StatusCode, message: &str) -> HttpResponse {
+    HttpResponse::build(status).json(json!({
+        "status": "error",
+        "message": message,
+    }))
+}
+
+fn replay(stored: StoredResponse) -> HttpResponse {
+    match stored {
+        StoredResponse::InProgress { .. } => error_response(
+            StatusCode::CONFLICT,
+            "A request with this Idempotency-Key is still being processed",
+        ),
+        StoredResponse::Completed {
+            status,
+            content_type,
+            headers,
+            body,
+            ..
+        } => {
+            let mut response = HttpResponse::build(
+                StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            );
+            if let Some(content_type) = content_type {
+                response.content_type(content_type);
+            }
+            for header in headers {
+                response.append_header(header);
+            }
+            response
+                .insert_header((IDEMPOTENT_REPLAYED_HEADER, "true"))
+                .body(body)
+        }
+    }
+}
+
+/// Makes POST and DELETE requests carrying an `Idempotency-Key` header safe to
+/// retry. The first response for a key is stored in Redis and replayed for
+/// every repeat of the same request until the key expires, so the handler runs
+/// at most once. Only final responses are stored: server errors and responses
+/// the client is told to retry are not, and neither is anything that sets a
+/// cookie, so session tokens never end up in Redis.
+pub async fn idempotency_middleware(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<BoxBody>, Error> {
+    if !matches!(*req.method(), Method::POST | Method::DELETE)
+        || req.path().starts_with(EXCLUDED_PATH_PREFIX)
+    {
+        return next
+            .call(req)
+            .await
+            .map(ServiceResponse::map_into_boxed_body);
+    }
+
+    let idempotency_key = match req.headers().get(IDEMPOTENCY_KEY_HEADER) {
+        Some(value) => match value.to_str() {
+            Ok(key) if !key.is_empty() && key.len() <= MAX_KEY_LENGTH => key.to_string(),
+            _ => {
+                return Ok(req.into_response(error_response(
+                    StatusCode::BAD_REQUEST,
+                    "Idempotency-Key must be 1 to 255 visible ASCII characters",
+                )))
+            }
+        },
+        None => {
+            return next
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_boxed_body)
+        }
+    };
+
+    let (redis_client, ttl_seconds) = match (
+        req.app_data::<web::Data<RedisClient>>(),
+        req.app_data::<web::Data<IdempotencyConfig>>(),
+    ) {
+        (Some(redis_client), Some(config)) => (redis_client.clone(), config.ttl_seconds),
+        _ => {
+            return next
+                .call(req)
+                .await
+                .map(ServiceResponse::map_into_boxed_body)
+        }
+    };
+
+    // Keys are scoped to the endpoint, and the request body is fingerprinted so
+    // reusing a key for a different request is rejected instead of replayed.
+    let request_body = req.extract::<web::Payload>().await?.to_bytes().await?;
+    let mut hasher = Sha256::new();
+    hasher.update(req.method().as_str());
+    hasher.update(req.path());
+    hasher.update(&request_body);
+    let fingerprint = hex::encode(hasher.finalize());
+    req.set_payload(request_body.into());
+
+    // Keys are also scoped to the caller, so one user can never be replayed
+    // another user's response.
+    let caller = req
+        .extensions()
+        .get::<Principal>()
+        .map(|principal| principal.user_id.clone())
+        .unwrap_or_default();
+    let key = format!(
+        "{}:{} {}:{}",
+        caller,
+        req.method(),
+        req.path(),
+        idempotency_key
+    );
+
+    match redis_client
+        .set_value_if_absent(
+            "idempotency",
+            &key,
+            &StoredResponse::InProgress {
+                fingerprint: fingerprint.clone(),
+            },
+            ttl_seconds,
+        )
+        .await
+    {
+        Ok(true) => {}
+        Ok(false) => {
+            let response = match redis_client
+                .get_value::<StoredResponse>("idempotency", &key)
+                .await
+            {
+                Ok(Some(stored)) if stored.fingerprint() != fingerprint => error_response(
+                    StatusCode::UNPROCESSABLE_ENTITY,
+                    "Idempotency-Key was already used for a different request",
+                ),
+                Ok(Some(stored)) => replay(stored),
+                _ => error_response(
+                    StatusCode::CONFLICT,
+                    "A request with this Idempotency-Key is still being processed",
+                ),
+            };
+            return Ok(req.into_response(response));
+        }
+        Err(err) => {
+            eprintln!("Failed to reserve idempotency key {}: {:?}", key, err);
+            return Ok(req.into_response(error_response(
+                StatusCode::SERVICE_UNAVAILABLE,
+                "Failed to check Idempotency-Key",
+            )));
+        }
+    }
+
+    let response = next.call(req).await;
+    let response = match response {
+        Ok(response) => response,
+        Err(err) => {
+            let _ = redis_client
+                .delete_key(&format!("idempotency:{}", key))
+                .await;
+            return Err(err);
+        }
+    };
+
+    let (req, response) = response.into_parts();
+    let (response, response_body) = response.into_parts();
+    let response_body = match body::to_bytes(response_body).await {
+        Ok(response_body) => response_body,
+        Err(_) => {
+            let _ = redis_client
+                .delete_key(&format!("idempotency:{}", key))
+                .await;
+            return Ok(ServiceResponse::new(
+                req,
+                error_response(
+                    StatusCode::INTERNAL_SERVER_ERROR,
+                    "Failed to read response body",
+                ),
+            ));
+        }
+    };
+
+    if !is_replayable(response.status()) || response.headers().contains_key(header::SET_COOKIE) {
+        let _ = redis_client
+            .delete_key(&format!("idempotency:{}", key))
+            .await;
+    } else {
+        let stored = StoredResponse::Completed {
+            fingerprint,
+            status: response.status().as_u16(),
+            content_type: response
+                .headers()
+                .get(header::CONTENT_TYPE)
+                .and_then(|value| value.to_str().ok())
+                .map(ToOwned::to_owned),
+            headers: response
+                .headers()
+                .iter()
+                .filter(|(name, _)| {
+                    !matches!(
+                        *name,
+                        &header::CONTENT_TYPE
+                            | &header::CONTENT_LENGTH
+                            | &header::DATE
+                            | &header::CONNECTION
+                            | &header::TRANSFER_ENCODING
+                    )
+                })
+                .filter_map(|(name, value)| {
+                    Some((name.to_string(), value.to_str().ok()?.to_string()))
+                })
+                .collect(),
+            body: String::from_utf8_lossy(&response_body).into_owned(),
+        };
+        if let Err(err) = redis_client
+            .set_value("idempotency", &key, &stored, Some(ttl_seconds))
+            .await
+        {
+            eprintln!("Failed to store idempotent response {}: {:?}", key, err);
+        }
+    }
+
+    Ok(ServiceResponse::new(
+        req,
+        response.set_body(BoxBody::new(response_body)),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn only_final_responses_are_replayable() {
+        assert!(is_replayable(StatusCode::OK));
+        assert!(is_replayable(StatusCode::CREATED));
+        assert!(is_replayable(StatusCode::BAD_REQUEST));
+        assert!(is_replayable(StatusCode::FORBIDDEN));
+        assert!(!is_replayable(StatusCode::CONFLICT));
+        assert!(!is_replayable(StatusCode::TOO_MANY_REQUESTS));
+        assert!(!is_replayable(StatusCode::REQUEST_TIMEOUT));
+        assert!(!is_replayable(StatusCode::INTERNAL_SERVER_ERROR));
+        assert!(!is_replayable(StatusCode::BAD_GATEWAY));
+    }
+
+    #[test]
+    fn responses_stored_before_headers_were_kept_still_replay() {
+        let stored: StoredResponse = serde_json::from_str(
+            r#"{"state": "Completed", "fingerprint": "f", "status": 201,
+                "content_type": "application/json", "body": "{}"}"#,
+        )
+        .unwrap();
+        let response = replay(stored);
+        assert_eq!(response.status(), StatusCode::CREATED);
+        assert_eq!(
+            response.headers().get(IDEMPOTENT_REPLAYED_HEADER).unwrap(),
+            "true"
+        );
+    }
+}
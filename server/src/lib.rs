@@ -1,7 +1,15 @@
+pub mod auth;
 pub mod awss3;
+pub mod backfill;
 pub mod config;
 pub mod elasticsearch;
 pub mod handlers;
+pub mod idempotency;
+pub mod money;
 pub mod mongo;
+pub mod notifications;
+pub mod receipts;
 pub mod redis;
+pub mod scheduling;
+pub mod siwe;
 pub mod types;
@@ -1,16 +1,30 @@
 use actix_cors::Cors;
-use actix_web::{middleware::Logger, web, App, HttpServer};
+use actix_web::{
+    middleware::{from_fn, Logger},
+    web, App, HttpServer,
+};
 use auction_server::{
+    auth::{authentication_middleware, require_admin_middleware},
     awss3::AWSClient,
+    backfill::{backfill_item_sellers, backfill_money_fields},
     config::Config,
     elasticsearch::ElasticSearchClient,
     handlers::*,
-    mongo::MongoClient,
+    idempotency::idempotency_middleware,
+    mongo::{MongoClient, Role},
+    notifications::{
+        deliver_notifications, queue_ending_soon_notifications, FileSender, LogSender,
+        NotificationSender,
+    },
+    receipts::ReceiptSigner,
     redis::RedisClient,
-    types::{BlockchainAPIURI, TransferSchedulerURI},
+    scheduling::activate_scheduled_items,
+    types::{
+        AuthConfig, BidIncrementRules, BidRateLimitConfig, BiddingConfig, BlockchainAPIURI,
+        BuyNowConfig, IdempotencyConfig, SignedBidConfig, SoftCloseConfig, TransferSchedulerURI,
+    },
 };
-use env_logger;
-use log::info;
+use log::{info, warn};
 
 fn initialise_logger() {
     std::env::set_var("RUST_LOG", "actix_web=info,auction_server=debug");
@@ -59,12 +73,134 @@ async fn main() -> std::io::Result<()> {
         uri: configurations.transfer_scheduler_base_uri,
     });
 
+    let bidding_config = web::Data::new(BiddingConfig {
+        increments: match &configurations.bid_increments {
+            Some(rules) => {
+                BidIncrementRules::from_json(rules).expect("Failed to parse bid increments")
+            }
+            None => BidIncrementRules::default(),
+        },
+        soft_close: SoftCloseConfig {
+            window_seconds: configurations.soft_close_window_seconds.unwrap_or(120),
+            extension_seconds: configurations.soft_close_extension_seconds.unwrap_or(120),
+        },
+        buy_now: BuyNowConfig {
+            disable_fraction: configurations
+                .buy_now_disable_fraction
+                .unwrap_or(0.5)
+                .min(1.0),
+        },
+        rate_limit: BidRateLimitConfig {
+            window_seconds: configurations.bid_rate_limit_window_seconds.unwrap_or(10),
+            bidder_limit: configurations.bid_rate_limit_per_bidder.unwrap_or(5),
+            item_limit: configurations.bid_rate_limit_per_item.unwrap_or(50),
+        },
+        signed_bids: SignedBidConfig {
+            threshold: configurations.signed_bid_threshold,
+            max_validity_seconds: configurations
+                .signed_bid_max_validity_seconds
+                .unwrap_or(15 * 60),
+        },
+    });
+
+    let idempotency_config = web::Data::new(IdempotencyConfig {
+        ttl_seconds: configurations
+            .idempotency_key_ttl_seconds
+            .unwrap_or(24 * 60 * 60),
+    });
+
+    let auth_config = web::Data::new(AuthConfig {
+        siwe_domain: configurations.siwe_domain.clone(),
+        siwe_chain_id: configurations.siwe_chain_id,
+        nonce_ttl_seconds: configurations.siwe_nonce_ttl_seconds.unwrap_or(5 * 60),
+        session_ttl_seconds: configurations
+            .session_ttl_seconds
+            .unwrap_or(7 * 24 * 60 * 60),
+    });
+
     initialise_logger();
 
+    let receipt_signer = web::Data::new(
+        ReceiptSigner::from_seed_hex(&configurations.receipt_signing_key)
+            .expect("Failed to load receipt signing key"),
+    );
+
     info!("Central server starting on app_port {}...", app_port);
 
+    if let Err(err) = mongo_client.ensure_indexes().await {
+        warn!("Failed to create indexes: {:?}", err);
+    }
+
+    // Admins listed in the configuration are granted the role on every start,
+    // further admins can then be added to the user_roles collection.
+    for admin in configurations
+        .admin_users
+        .as_deref()
+        .unwrap_or_default()
+        .split(',')
+        .map(str::trim)
+        .filter(|admin| !admin.is_empty())
+    {
+        if let Err(err) = mongo_client.grant_role(admin, Role::ADMIN).await {
+            warn!("Failed to grant admin role to {}: {:?}", admin, err);
+        }
+    }
+
+    let backfill_mongo_client = mongo_client.clone();
+    let backfill_blockchain_uri = blockchain_base_uri.uri.clone();
+    tokio::spawn(async move {
+        backfill_item_sellers(&backfill_mongo_client, &backfill_blockchain_uri).await;
+    });
+
+    let money_mongo_client = mongo_client.clone();
+    let money_redis_client = redis_client.clone();
+    tokio::spawn(async move {
+        backfill_money_fields(&money_mongo_client, &money_redis_client).await;
+    });
+
+    let activation_mongo_client = mongo_client.clone();
+    let activation_redis_client = redis_client.clone();
+    let activation_elasticsearch_client = elasticsearch_client.clone();
+    tokio::spawn(async move {
+        activate_scheduled_items(
+            &activation_mongo_client,
+            &activation_redis_client,
+            &activation_elasticsearch_client,
+        )
+        .await;
+    });
+
+    let notification_sender: Box<dyn NotificationSender> =
+        match configurations.notification_sink.as_deref() {
+            Some("file") => Box::new(FileSender::new(
+                configurations
+                    .notification_file
+                    .clone()
+                    .unwrap_or_else(|| "notifications.log".to_string()),
+            )),
+            _ => Box::new(LogSender),
+        };
+    let notification_redis_client = redis_client.clone();
+    tokio::spawn(async move {
+        deliver_notifications(&notification_redis_client, notification_sender.as_ref()).await;
+    });
+
+    let ending_soon_window_seconds = configurations.ending_soon_window_seconds.unwrap_or(600);
+    let ending_soon_mongo_client = mongo_client.clone();
+    let ending_soon_redis_client = redis_client.clone();
+    tokio::spawn(async move {
+        queue_ending_soon_notifications(
+            &ending_soon_mongo_client,
+            &ending_soon_redis_client,
+            ending_soon_window_seconds,
+        )
+        .await;
+    });
+
     let server = HttpServer::new(move || {
         App::new()
+            .wrap(from_fn(idempotency_middleware))
+            .wrap(from_fn(authentication_middleware))
             .wrap(Logger::default())
             .wrap(
                 Cors::default()
@@ -75,11 +211,19 @@ async fn main() -> std::io::Result<()> {
                     .max_age(3600),
             )
             .service(health_check_handler)
+            .service(auth_nonce_handler)
+            .service(auth_verify_handler)
             .service(post_item_handler)
             .service(get_item_handler)
+            .service(get_item_bids_handler)
+            .service(get_item_events_handler)
+            .service(get_sealed_bid_handler)
             .service(get_home_page_handler)
             .service(get_operation_status_handler)
+            .service(get_receipt_keys_handler)
+            .service(verify_receipt_handler)
             .service(place_bid_handler)
+            .service(buy_now_handler)
             .service(get_user_items_handler)
             .service(delete_item_handler)
             .service(transfer_item_handler)
@@ -87,12 +231,33 @@ async fn main() -> std::io::Result<()> {
             .service(autocomplete_item_handler)
             .service(search_item_handler)
             .service(get_category_items_handler)
+            .service(add_to_watchlist_handler)
+            .service(remove_from_watchlist_handler)
+            .service(get_watchlist_handler)
+            .service(create_saved_search_handler)
+            .service(get_saved_searches_handler)
+            .service(get_search_alerts_handler)
+            .service(pause_saved_search_handler)
+            .service(delete_saved_search_handler)
+            .service(
+                web::scope("/api/v1/admin")
+                    .wrap(from_fn(require_admin_middleware))
+                    .service(admin_end_auction_handler)
+                    .service(admin_take_down_item_handler)
+                    .service(admin_ban_bidder_handler)
+                    .service(admin_unban_bidder_handler)
+                    .service(admin_get_failed_operations_handler),
+            )
             .app_data(redis_client.clone())
             .app_data(mongo_client.clone())
             .app_data(aws_client.clone())
             .app_data(elasticsearch_client.clone())
             .app_data(blockchain_base_uri.clone())
             .app_data(transfer_scheduler_base_uri.clone())
+            .app_data(bidding_config.clone())
+            .app_data(idempotency_config.clone())
+            .app_data(auth_config.clone())
+            .app_data(receipt_signer.clone())
     })
     .bind((configurations.app.host, app_port))?;
 
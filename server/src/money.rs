@@ -0,0 +1,386 @@
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{cmp::Ordering, fmt};
+
+// Active ISO 4217 currencies, sorted. Fund codes, precious metals and the
+// testing codes are left out, as nothing is ever priced in them here.
+const ISO_4217_CODES: [&str; 160] = [
+    "AED", "AFN", "ALL", "AMD", "ANG", "AOA", "ARS", "AUD", "AWG", "AZN", "BAM", "BBD", "BDT",
+    "BGN", "BHD", "BIF", "BMD", "BND", "BOB", "BRL", "BSD", "BTN", "BWP", "BYN", "BZD", "CAD",
+    "CDF", "CHF", "CLP", "CNY", "COP", "CRC", "CUC", "CUP", "CVE", "CZK", "DJF", "DKK", "DOP",
+    "DZD", "EGP", "ERN", "ETB", "EUR", "FJD", "FKP", "GBP", "GEL", "GHS", "GIP", "GMD", "GNF",
+    "GTQ", "GYD", "HKD", "HNL", "HTG", "HUF", "IDR", "ILS", "INR", "IQD", "IRR", "ISK", "JMD",
+    "JOD", "JPY", "KES", "KGS", "KHR", "KMF", "KPW", "KRW", "KWD", "KYD", "KZT", "LAK", "LBP",
+    "LKR", "LRD", "LSL", "LYD", "MAD", "MDL", "MGA", "MKD", "MMK", "MNT", "MOP", "MRU", "MUR",
+    "MVR", "MWK", "MXN", "MYR", "MZN", "NAD", "NGN", "NIO", "NOK", "NPR", "NZD", "OMR", "PAB",
+    "PEN", "PGK", "PHP", "PKR", "PLN", "PYG", "QAR", "RON", "RSD", "RUB", "RWF", "SAR", "SBD",
+    "SCR", "SDG", "SEK", "SGD", "SHP", "SLE", "SLL", "SOS", "SRD", "SSP", "STN", "SVC", "SYP",
+    "SZL", "THB", "TJS", "TMT", "TND", "TOP", "TRY", "TTD", "TWD", "TZS", "UAH", "UGX", "USD",
+    "UYU", "UZS", "VED", "VES", "VND", "VUV", "WST", "XAF", "XCD", "XCG", "XOF", "XPF", "YER",
+    "ZAR", "ZMW", "ZWG", "ZWL",
+];
+
+/// ISO 4217 currency code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Currency([u8; 3]);
+
+impl Currency {
+    pub const USD: Currency = Currency(*b"USD");
+
+    pub fn parse(code: &str) -> Option<Self> {
+        let code = code.trim().to_ascii_uppercase();
+        ISO_4217_CODES.binary_search(&code.as_str()).ok()?;
+        let bytes: [u8; 3] = code.as_bytes().try_into().ok()?;
+        Some(Currency(bytes))
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("USD")
+    }
+
+    /// Number of digits after the decimal point in the currency's minor unit.
+    pub fn exponent(&self) -> u32 {
+        match self.as_str() {
+            "BIF" | "CLP" | "DJF" | "GNF" | "ISK" | "JPY" | "KMF" | "KRW" | "PYG" | "RWF"
+            | "UGX" | "VND" | "VUV" | "XAF" | "XOF" | "XPF" => 0,
+            "BHD" | "IQD" | "JOD" | "KWD" | "LYD" | "OMR" | "TND" => 3,
+            _ => 2,
+        }
+    }
+
+    fn scale(&self) -> i64 {
+        10_i64.pow(self.exponent())
+    }
+}
+
+impl Default for Currency {
+    fn default() -> Self {
+        Currency::USD
+    }
+}
+
+impl fmt::Display for Currency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl Serialize for Currency {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Currency {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let code = String::deserialize(deserializer)?;
+        Currency::parse(&code)
+            .ok_or_else(|| de::Error::custom(format!("invalid currency code: {}", code)))
+    }
+}
+
+/// An exact amount of money, held as an integer number of minor units
+/// (cents for USD) so arithmetic and storage never go through floats.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct Money {
+    pub minor_units: i64,
+    pub currency: Currency,
+}
+
+impl Money {
+    pub fn new(minor_units: i64, currency: Currency) -> Self {
+        Money {
+            minor_units,
+            currency,
+        }
+    }
+
+    pub fn zero(currency: Currency) -> Self {
+        Money::new(0, currency)
+    }
+
+    /// Converts a float amount in major units, rounding to the nearest minor unit.
+    /// Only meant for legacy data and configuration values.
+    pub fn from_major(amount: f64, currency: Currency) -> Option<Self> {
+        let minor_units = (amount * currency.scale() as f64).round();
+        (minor_units.is_finite() && minor_units.abs() < i64::MAX as f64)
+            .then(|| Money::new(minor_units as i64, currency))
+    }
+
+    /// Parses a decimal string such as `"12.50"`, rejecting more fractional
+    /// digits than the currency has.
+    pub fn parse(amount: &str, currency: Currency) -> Option<Self> {
+        let amount = amount.trim();
+        let (negative, amount) = match amount.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, amount),
+        };
+        let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+        let exponent = currency.exponent() as usize;
+        if whole.is_empty()
+            || fraction.len() > exponent
+            || !whole.chars().all(|c| c.is_ascii_digit())
+            || !fraction.chars().all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+
+        let whole: i64 = whole.parse().ok()?;
+        let fraction: i64 = format!("{:0<width$}", fraction, width = exponent)
+            .parse()
+            .unwrap_or(0);
+        let minor_units = whole.checked_mul(currency.scale())?.checked_add(fraction)?;
+        Some(Money::new(
+            if negative { -minor_units } else { minor_units },
+            currency,
+        ))
+    }
+
+    /// Approximate value in major units, for ratios and display only.
+    pub fn to_major(&self) -> f64 {
+        self.minor_units as f64 / self.currency.scale() as f64
+    }
+
+    pub fn is_positive(&self) -> bool {
+        self.minor_units > 0
+    }
+
+    /// The larger of two amounts in the same currency; `self` wins on a mismatch.
+    pub fn max(self, other: Money) -> Money {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    pub fn checked_add(self, other: Money) -> Option<Money> {
+        (self.currency == other.currency)
+            .then(|| self.minor_units.checked_add(other.minor_units))
+            .flatten()
+            .map(|minor_units| Money::new(minor_units, self.currency))
+    }
+
+    pub fn checked_sub(self, other: Money) -> Option<Money> {
+        (self.currency == other.currency)
+            .then(|| self.minor_units.checked_sub(other.minor_units))
+            .flatten()
+            .map(|minor_units| Money::new(minor_units, self.currency))
+    }
+
+    pub fn checked_mul(self, factor: i64) -> Option<Money> {
+        self.minor_units
+            .checked_mul(factor)
+            .map(|minor_units| Money::new(minor_units, self.currency))
+    }
+}
+
+impl PartialOrd for Money {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        (self.currency == other.currency).then(|| self.minor_units.cmp(&other.minor_units))
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = self.currency.exponent() as usize;
+        let sign = if self.minor_units < 0 { "-" } else { "" };
+        let minor_units = self.minor_units.unsigned_abs();
+        let scale = self.currency.scale() as u64;
+        if exponent == 0 {
+            write!(f, "{}{}", sign, minor_units)
+        } else {
+            write!(
+                f,
+                "{}{}.{:0width$}",
+                sign,
+                minor_units / scale,
+                minor_units % scale,
+                width = exponent
+            )
+        }
+    }
+}
+
+// Amounts written before money was stored exactly are plain float numbers in
+// US dollars, so those are still accepted when reading.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredMoney {
+    Exact {
+        minor_units: i64,
+        currency: Currency,
+    },
+    Legacy(f64),
+}
+
+impl<'de> Deserialize<'de> for Money {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match StoredMoney::deserialize(deserializer)? {
+            StoredMoney::Exact {
+                minor_units,
+                currency,
+            } => Ok(Money::new(minor_units, currency)),
+            StoredMoney::Legacy(amount) => Money::from_major(amount, Currency::USD)
+                .ok_or_else(|| de::Error::custom("invalid legacy amount")),
+        }
+    }
+}
+
+/// A price as sent by clients: an exact decimal string, or a count of minor
+/// units given as `{"minor_units": 1250}`. Bare JSON numbers are refused, as
+/// older clients sent them as float dollar amounts. The currency comes from
+/// the item it applies to.
+#[derive(Debug, Clone)]
+pub enum PriceInput {
+    Decimal(String),
+    MinorUnits(i64),
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawPriceInput {
+    Decimal(String),
+    MinorUnits { minor_units: i64 },
+}
+
+impl<'de> Deserialize<'de> for PriceInput {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawPriceInput::deserialize(deserializer) {
+            Ok(RawPriceInput::Decimal(amount)) => Ok(PriceInput::Decimal(amount)),
+            Ok(RawPriceInput::MinorUnits { minor_units }) => {
+                Ok(PriceInput::MinorUnits(minor_units))
+            }
+            Err(_) => Err(de::Error::custom(
+                "prices must be a decimal string such as \"12.50\" or {\"minor_units\": 1250}",
+            )),
+        }
+    }
+}
+
+impl PriceInput {
+    pub fn to_money(&self, currency: Currency) -> Option<Money> {
+        match self {
+            PriceInput::Decimal(amount) => Money::parse(amount, currency),
+            PriceInput::MinorUnits(minor_units) => Some(Money::new(*minor_units, currency)),
+        }
+    }
+
+    /// Converts an optional price that must be positive when present. The
+    /// outer `None` means a price was given but is not valid in `currency`.
+    pub fn to_optional_money(
+        price: Option<&PriceInput>,
+        currency: Currency,
+    ) -> Option<Option<Money>> {
+        match price {
+            Some(price) => price
+                .to_money(currency)
+                .filter(Money::is_positive)
+                .map(Some),
+            None => Some(None),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn currency(code: &str) -> Currency {
+        Currency::parse(code).unwrap()
+    }
+
+    #[test]
+    fn currency_codes_are_checked_against_iso_4217() {
+        assert!(ISO_4217_CODES.windows(2).all(|pair| pair[0] < pair[1]));
+        assert_eq!(Currency::parse(" eur "), Some(currency("EUR")));
+        assert_eq!(Currency::parse("XYZ"), None);
+        assert_eq!(Currency::parse("US"), None);
+        assert_eq!(Currency::parse("XAU"), None);
+    }
+
+    #[test]
+    fn parses_up_to_the_currency_exponent() {
+        let usd = Currency::USD;
+        assert_eq!(Money::parse("12.5", usd), Some(Money::new(1250, usd)));
+        assert_eq!(Money::parse("12.05", usd), Some(Money::new(1205, usd)));
+        assert_eq!(Money::parse("12", usd), Some(Money::new(1200, usd)));
+        assert_eq!(Money::parse("-0.01", usd), Some(Money::new(-1, usd)));
+        assert_eq!(Money::parse("12.005", usd), None);
+        assert_eq!(
+            Money::parse("1.005", currency("KWD")),
+            Some(Money::new(1005, currency("KWD")))
+        );
+        assert_eq!(
+            Money::parse("500", currency("JPY")),
+            Some(Money::new(500, currency("JPY")))
+        );
+        assert_eq!(Money::parse("500.0", currency("JPY")), None);
+    }
+
+    #[test]
+    fn rejects_malformed_amounts() {
+        let usd = Currency::USD;
+        for amount in ["", "-", ".50", "1.2.3", "1e3", "+1", "1,000", "--1"] {
+            assert_eq!(Money::parse(amount, usd), None, "{:?}", amount);
+        }
+        assert_eq!(Money::parse("92233720368547758.08", usd), None);
+    }
+
+    #[test]
+    fn formats_with_the_currency_exponent() {
+        assert_eq!(Money::new(1205, Currency::USD).to_string(), "12.05");
+        assert_eq!(Money::new(7, Currency::USD).to_string(), "0.07");
+        assert_eq!(Money::new(-150, Currency::USD).to_string(), "-1.50");
+        assert_eq!(Money::new(1005, currency("KWD")).to_string(), "1.005");
+        assert_eq!(Money::new(500, currency("JPY")).to_string(), "500");
+    }
+
+    #[test]
+    fn formatting_round_trips_through_parse() {
+        for (minor_units, code) in [(0, "USD"), (99, "EUR"), (-12345, "BHD"), (42, "JPY")] {
+            let money = Money::new(minor_units, currency(code));
+            assert_eq!(
+                Money::parse(&money.to_string(), money.currency),
+                Some(money)
+            );
+        }
+    }
+
+    #[test]
+    fn arithmetic_refuses_mixed_currencies() {
+        let dollar = Money::new(100, Currency::USD);
+        let euro = Money::new(100, currency("EUR"));
+        assert_eq!(dollar.checked_add(euro), None);
+        assert_eq!(dollar.checked_sub(euro), None);
+        assert_eq!(dollar.partial_cmp(&euro), None);
+        assert_eq!(
+            dollar.checked_add(Money::new(1, Currency::USD)),
+            Some(Money::new(101, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn reads_exact_and_legacy_amounts() {
+        let exact: Money =
+            serde_json::from_str(r#"{"minor_units": 1250, "currency": "EUR"}"#).unwrap();
+        assert_eq!(exact, Money::new(1250, currency("EUR")));
+        let legacy: Money = serde_json::from_str("12.5").unwrap();
+        assert_eq!(legacy, Money::new(1250, Currency::USD));
+    }
+
+    #[test]
+    fn price_input_refuses_bare_numbers() {
+        let decimal: PriceInput = serde_json::from_str(r#""12.50""#).unwrap();
+        assert_eq!(
+            decimal.to_money(Currency::USD),
+            Some(Money::new(1250, Currency::USD))
+        );
+        let minor_units: PriceInput = serde_json::from_str(r#"{"minor_units": 1250}"#).unwrap();
+        assert_eq!(
+            minor_units.to_money(Currency::USD),
+            Some(Money::new(1250, Currency::USD))
+        );
+        assert!(serde_json::from_str::<PriceInput>("12.5").is_err());
+        assert!(serde_json::from_str::<PriceInput>("100").is_err());
+    }
+}
@@ -1,27 +1,95 @@
 use bson::oid::ObjectId;
-use bson::DateTime;
-use mongodb::{options::ClientOptions, Client, Database};
+use bson::{DateTime, Document};
+use chrono::Utc;
+use futures::TryStreamExt;
+use mongodb::{
+    error::{ErrorKind, WriteFailure},
+    options::{ClientOptions, IndexOptions, UpdateOptions},
+    Client, Database, IndexModel,
+};
 use mongodm::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::string::ToString;
+use std::{collections::HashMap, fmt};
+
+use crate::money::{Currency, Money};
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub enum ItemStatus {
     PENDING,
+    SCHEDULED,
     ACTIVE,
     TRANSFERRING,
     SOLD,
     UNSOLD,
+    REMOVED,
+}
+
+impl fmt::Display for ItemStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            ItemStatus::PENDING => "PENDING",
+            ItemStatus::SCHEDULED => "SCHEDULED",
+            ItemStatus::ACTIVE => "ACTIVE",
+            ItemStatus::TRANSFERRING => "TRANSFERRING",
+            ItemStatus::SOLD => "SOLD",
+            ItemStatus::UNSOLD => "UNSOLD",
+            ItemStatus::REMOVED => "REMOVED",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub enum AuctionType {
+    #[default]
+    ENGLISH,
+    DUTCH,
+    SEALED_FIRST_PRICE,
+    SEALED_SECOND_PRICE,
+}
+
+impl AuctionType {
+    pub fn is_sealed(&self) -> bool {
+        matches!(
+            self,
+            AuctionType::SEALED_FIRST_PRICE | AuctionType::SEALED_SECOND_PRICE
+        )
+    }
+}
+
+impl fmt::Display for AuctionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            AuctionType::ENGLISH => "ENGLISH",
+            AuctionType::DUTCH => "DUTCH",
+            AuctionType::SEALED_FIRST_PRICE => "SEALED_FIRST_PRICE",
+            AuctionType::SEALED_SECOND_PRICE => "SEALED_SECOND_PRICE",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct DutchSchedule {
+    pub start_price: Money,
+    pub floor_price: Money,
+    pub decrement: Money,
+    pub interval_seconds: i64,
+    pub starts_at: DateTime,
 }
 
-impl ToString for ItemStatus {
-    fn to_string(&self) -> String {
-        match self {
-            ItemStatus::PENDING => "PENDING".to_string(),
-            ItemStatus::ACTIVE => "ACTIVE".to_string(),
-            ItemStatus::TRANSFERRING => "TRANSFERRING".to_string(),
-            ItemStatus::SOLD => "SOLD".to_string(),
-            ItemStatus::UNSOLD => "UNSOLD".to_string(),
+impl DutchSchedule {
+    pub fn price_at(&self, now: chrono::DateTime<Utc>) -> Money {
+        let elapsed = (now - self.starts_at.to_chrono()).num_seconds().max(0);
+        let steps = elapsed / self.interval_seconds.max(1);
+        match self
+            .decrement
+            .checked_mul(steps)
+            .and_then(|dropped| self.start_price.checked_sub(dropped))
+        {
+            Some(price) if price > self.floor_price => price,
+            _ => self.floor_price,
         }
     }
 }
@@ -34,10 +102,104 @@ pub struct Item {
     pub description: String,
     pub images: Vec<String>,
     pub category: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auction_start: Option<DateTime>,
     pub auction_end: DateTime,
     pub rating: f64,
     pub status: ItemStatus,
-    pub base_price: f64,
+    pub base_price: Money,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reserve_price: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub buy_now_price: Option<Money>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seller: Option<String>,
+    #[serde(default)]
+    pub auction_type: AuctionType,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dutch_schedule: Option<DutchSchedule>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clearing_price: Option<Money>,
+    #[serde(default = "default_quantity")]
+    pub quantity: u32,
+    /// Set on new listings until saved searches have been alerted, which
+    /// happens once the item goes ACTIVE.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub search_alerts_pending: bool,
+}
+
+fn default_quantity() -> u32 {
+    1
+}
+
+/// Matches items whose auction window is currently open: active items, plus
+/// scheduled ones whose start time has passed but have not been promoted yet.
+pub fn open_auction_filter() -> Document {
+    let now = DateTime::now();
+    doc! {
+        "$or": [
+            {"status": ItemStatus::ACTIVE.to_string()},
+            {"status": ItemStatus::SCHEDULED.to_string(), "auction_start": {"$lte": now}},
+        ],
+        "auction_end": {"$gt": now},
+    }
+}
+
+impl Item {
+    pub fn is_open(&self, now: chrono::DateTime<Utc>) -> bool {
+        let started = match self.status {
+            ItemStatus::ACTIVE => true,
+            ItemStatus::SCHEDULED => self
+                .auction_start
+                .is_some_and(|auction_start| auction_start.to_chrono() <= now),
+            _ => false,
+        };
+        started && self.auction_end.to_chrono() > now
+    }
+
+    pub fn is_lot(&self) -> bool {
+        self.quantity > 1
+    }
+
+    pub fn is_seller(&self, user_id: &str) -> bool {
+        self.seller
+            .as_ref()
+            .is_some_and(|seller| seller.eq_ignore_ascii_case(user_id))
+    }
+
+    pub fn currency(&self) -> Currency {
+        self.base_price.currency
+    }
+
+    pub fn reserve_met(&self, current_bid_price: Option<Money>) -> Option<bool> {
+        self.reserve_price
+            .map(|reserve_price| current_bid_price.is_some_and(|price| price >= reserve_price))
+    }
+
+    pub fn dutch_price(&self, now: chrono::DateTime<Utc>) -> Option<Money> {
+        match self.auction_type {
+            AuctionType::DUTCH => self
+                .dutch_schedule
+                .as_ref()
+                .map(|schedule| schedule.price_at(now)),
+            _ => None,
+        }
+    }
+
+    pub fn to_public_json(&self, current_bid_price: Option<Money>) -> serde_json::Value {
+        let mut value = serde_json::to_value(self).unwrap_or_default();
+        if let Some(fields) = value.as_object_mut() {
+            fields.remove("reserve_price");
+            fields.insert(
+                "reserve_met".to_string(),
+                serde_json::json!(self.reserve_met(current_bid_price)),
+            );
+            if let Some(dutch_price) = self.dutch_price(Utc::now()) {
+                fields.insert("dutch_price".to_string(), serde_json::json!(dutch_price));
+            }
+        }
+        value
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -46,8 +208,144 @@ pub struct Bid {
     pub id: Option<ObjectId>,
     pub item_id: String,
     pub bidder: String,
-    pub bid_price: f64,
+    pub bid_price: Money,
     pub timestamp: DateTime,
+    #[serde(default)]
+    pub proxy: bool,
+    #[serde(default)]
+    pub buy_now: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub quantity: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<BidSignature>,
+}
+
+/// A bidder's wallet signature authorising bids on an item up to `amount`.
+/// The exact signed message is kept so the proof can be checked independently.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidSignature {
+    pub signer: String,
+    pub amount: Money,
+    pub nonce: String,
+    pub expires_at: DateTime,
+    pub message: String,
+    pub signature: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ProxyBid {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub item_id: String,
+    pub bidder: String,
+    pub max_bid: Money,
+    pub increment: Money,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signature: Option<BidSignature>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct LotBid {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub item_id: String,
+    pub bidder: String,
+    pub quantity: u32,
+    pub bid_price: Money,
+    pub created_at: DateTime,
+    pub updated_at: DateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WatchlistEntry {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: String,
+    pub item_id: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SavedSearchFilters {
+    pub category: Option<String>,
+    pub auction_type: Option<AuctionType>,
+    pub max_price: Option<Money>,
+}
+
+impl SavedSearchFilters {
+    /// The text query is matched by Elasticsearch; these filters are checked on the item itself.
+    pub fn matches(&self, item: &Item) -> bool {
+        self.category
+            .as_ref()
+            .is_none_or(|category| category.eq_ignore_ascii_case(&item.category))
+            && self
+                .auction_type
+                .as_ref()
+                .is_none_or(|auction_type| *auction_type == item.auction_type)
+            && self
+                .max_price
+                .is_none_or(|max_price| item.base_price <= max_price)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SavedSearch {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub user_id: String,
+    pub query: String,
+    #[serde(default)]
+    pub filters: SavedSearchFilters,
+    #[serde(default)]
+    pub paused: bool,
+    pub created_at: DateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SearchAlert {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub saved_search_id: ObjectId,
+    pub user_id: String,
+    pub item_id: String,
+    pub item_title: String,
+    pub created_at: DateTime,
+}
+
+#[derive(Debug, Default)]
+pub struct LotAllocation {
+    pub winners: Vec<(String, u32)>,
+    pub units_allocated: u32,
+    pub clearing_price: Option<Money>,
+}
+
+impl LotAllocation {
+    /// Fills `quantity` units from bids ranked by price (highest first, earliest
+    /// update breaking ties). The last bid to receive units sets the uniform
+    /// price that every winner pays, and it may be only partially filled.
+    pub fn from_ranked_bids(quantity: u32, ranked_bids: &[LotBid]) -> Self {
+        let mut allocation = LotAllocation::default();
+        for bid in ranked_bids {
+            let remaining = quantity - allocation.units_allocated;
+            if remaining == 0 {
+                break;
+            }
+            let units = bid.quantity.min(remaining);
+            allocation.winners.push((bid.bidder.clone(), units));
+            allocation.units_allocated += units;
+            allocation.clearing_price = Some(bid.bid_price);
+        }
+        allocation
+    }
+
+    pub fn units_for(&self, bidder: &str) -> u32 {
+        self.winners
+            .iter()
+            .find(|(winner, _)| winner == bidder)
+            .map_or(0, |(_, units)| *units)
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -57,13 +355,14 @@ pub enum OperationType {
     DELETE,
 }
 
-impl ToString for OperationType {
-    fn to_string(&self) -> String {
-        match self {
-            OperationType::ADD => "ADD".to_string(),
-            OperationType::DELETE => "DELETE".to_string(),
-            OperationType::TRANSFER => "TRANSFER".to_string(),
-        }
+impl fmt::Display for OperationType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            OperationType::ADD => "ADD",
+            OperationType::DELETE => "DELETE",
+            OperationType::TRANSFER => "TRANSFER",
+        };
+        write!(f, "{}", value)
     }
 }
 
@@ -74,13 +373,14 @@ pub enum OperationStatus {
     FAILED,
 }
 
-impl ToString for OperationStatus {
-    fn to_string(&self) -> String {
-        match self {
-            OperationStatus::PENDING => "PENDING".to_string(),
-            OperationStatus::COMPLETED => "COMPLETED".to_string(),
-            OperationStatus::FAILED => "FAILED".to_string(),
-        }
+impl fmt::Display for OperationStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            OperationStatus::PENDING => "PENDING",
+            OperationStatus::COMPLETED => "COMPLETED",
+            OperationStatus::FAILED => "FAILED",
+        };
+        write!(f, "{}", value)
     }
 }
 
@@ -98,6 +398,99 @@ pub struct Operation {
     pub updated_at: DateTime,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum Role {
+    ADMIN,
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            Role::ADMIN => "ADMIN",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// The roles granted to a user. User ids are stored lowercased, as addresses
+/// arrive in mixed case.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct UserRoles {
+    #[serde(rename = "_id")]
+    pub user_id: String,
+    #[serde(default)]
+    pub roles: Vec<Role>,
+    pub updated_at: DateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BiddingBan {
+    #[serde(rename = "_id")]
+    pub user_id: String,
+    pub reason: Option<String>,
+    pub banned_by: String,
+    pub created_at: DateTime,
+}
+
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AdminAction {
+    FORCE_END_AUCTION,
+    TAKE_DOWN_ITEM,
+    BAN_BIDDER,
+    UNBAN_BIDDER,
+    VIEW_FAILED_OPERATIONS,
+}
+
+/// Entries are written as `ATTEMPTED` before the action runs, then marked with
+/// how it ended. Entries from before outcomes were tracked were only written
+/// for actions that had succeeded.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Default)]
+pub enum AuditOutcome {
+    ATTEMPTED,
+    #[default]
+    SUCCEEDED,
+    FAILED,
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = match self {
+            AuditOutcome::ATTEMPTED => "ATTEMPTED",
+            AuditOutcome::SUCCEEDED => "SUCCEEDED",
+            AuditOutcome::FAILED => "FAILED",
+        };
+        write!(f, "{}", value)
+    }
+}
+
+/// One entry per admin action, written to the `admin_audit` collection.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AuditEntry {
+    #[serde(rename = "_id", skip_serializing_if = "Option::is_none")]
+    pub id: Option<ObjectId>,
+    pub actor: String,
+    pub action: AdminAction,
+    pub target: Option<String>,
+    pub reason: Option<String>,
+    #[serde(default)]
+    pub outcome: AuditOutcome,
+    /// What went wrong, for actions that failed or only partly succeeded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+    pub created_at: DateTime,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub finished_at: Option<DateTime>,
+}
+
+/// Whether a write was rejected by a unique index.
+pub fn is_duplicate_key_error(err: &mongodb::error::Error) -> bool {
+    matches!(
+        err.kind.as_ref(),
+        ErrorKind::Write(WriteFailure::WriteError(write_error)) if write_error.code == 11000
+    )
+}
+
 #[derive(Clone)]
 pub struct MongoClient {
     db: Database,
@@ -126,4 +519,406 @@ impl MongoClient {
     pub fn get_db(&self) -> Database {
         self.db.clone()
     }
+
+    /// Creates the indexes that uniqueness guarantees rely on. Creating an
+    /// index that already exists is a no-op, so this runs on every start.
+    pub async fn ensure_indexes(&self) -> Result<(), MongoError> {
+        self.db
+            .collection::<WatchlistEntry>("watchlists")
+            .create_index(
+                IndexModel::builder()
+                    .keys(doc! {"user_id": 1, "item_id": 1})
+                    .options(IndexOptions::builder().unique(true).build())
+                    .build(),
+                None,
+            )
+            .await
+            .map_err(MongoError::OperationError)?;
+        Ok(())
+    }
+
+    pub async fn activate_scheduled_items(&self) -> Result<Vec<String>, MongoError> {
+        let items = self.db.collection::<Item>("items");
+        let filter = doc! {
+            "status": ItemStatus::SCHEDULED.to_string(),
+            "auction_start": {"$lte": DateTime::now()},
+        };
+
+        let item_ids: Vec<String> = items
+            .find(filter.clone(), None)
+            .await
+            .map_err(MongoError::OperationError)?
+            .try_collect::<Vec<Item>>()
+            .await
+            .map_err(MongoError::OperationError)?
+            .into_iter()
+            .filter_map(|item| item.id)
+            .collect();
+
+        if item_ids.is_empty() {
+            return Ok(item_ids);
+        }
+
+        let mut filter = filter;
+        filter.insert("_id", doc! {"$in": &item_ids});
+        items
+            .update_many(
+                filter,
+                doc! {"$set": {"status": ItemStatus::ACTIVE.to_string()}},
+                None,
+            )
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(item_ids)
+    }
+
+    /// Takes the items that went ACTIVE since their saved search alerts were
+    /// last sent. Each item is claimed atomically, so it is only returned once.
+    pub async fn claim_items_awaiting_search_alerts(&self) -> Result<Vec<Item>, MongoError> {
+        let items = self.db.collection::<Item>("items");
+        let mut claimed = vec![];
+
+        while let Some(item) = items
+            .find_one_and_update(
+                doc! {
+                    "status": ItemStatus::ACTIVE.to_string(),
+                    "search_alerts_pending": true,
+                },
+                doc! {"$unset": {"search_alerts_pending": ""}},
+                None,
+            )
+            .await
+            .map_err(MongoError::OperationError)?
+        {
+            claimed.push(item);
+        }
+
+        Ok(claimed)
+    }
+
+    /// Open auctions that close within `window` from now.
+    pub async fn find_items_ending_within(
+        &self,
+        window: chrono::Duration,
+    ) -> Result<Vec<Item>, MongoError> {
+        let now = Utc::now();
+        let mut filter = open_auction_filter();
+        filter.insert(
+            "auction_end",
+            doc! {
+                "$gt": DateTime::from_chrono(now),
+                "$lte": DateTime::from_chrono(now + window),
+            },
+        );
+
+        self.db
+            .collection::<Item>("items")
+            .find(filter, None)
+            .await
+            .map_err(MongoError::OperationError)?
+            .try_collect()
+            .await
+            .map_err(MongoError::OperationError)
+    }
+
+    /// Everyone who has placed a bid on the item.
+    pub async fn find_item_bidders(&self, item_id: &str) -> Result<Vec<String>, MongoError> {
+        let bidders = self
+            .db
+            .collection::<Bid>("bids")
+            .distinct("bidder", doc! {"item_id": item_id}, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(bidders
+            .into_iter()
+            .filter_map(|bidder| bidder.as_str().map(ToOwned::to_owned))
+            .collect())
+    }
+
+    /// Number of users watching each of the given items. Items nobody watches are left out.
+    pub async fn count_watchers(
+        &self,
+        item_ids: &[String],
+    ) -> Result<HashMap<String, u64>, MongoError> {
+        if item_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+
+        let counts: Vec<Document> = self
+            .db
+            .collection::<WatchlistEntry>("watchlists")
+            .aggregate(
+                vec![
+                    doc! {"$match": {"item_id": {"$in": item_ids}}},
+                    doc! {"$group": {"_id": "$item_id", "count": {"$sum": 1}}},
+                ],
+                None,
+            )
+            .await
+            .map_err(MongoError::OperationError)?
+            .try_collect()
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(counts
+            .into_iter()
+            .filter_map(|count| {
+                let item_id = count.get_str("_id").ok()?.to_string();
+                let watchers = count.get_i32("count").ok()?;
+                Some((item_id, watchers.max(0) as u64))
+            })
+            .collect())
+    }
+
+    pub async fn find_item_seller(&self, item_id: &str) -> Result<Option<String>, MongoError> {
+        let item = self
+            .db
+            .collection::<Item>("items")
+            .find_one(doc! {"_id": item_id}, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        if let Some(seller) = item.and_then(|item| item.seller) {
+            return Ok(Some(seller));
+        }
+
+        let operation = self
+            .db
+            .collection::<Operation>("operations")
+            .find_one(doc! {"type": "ADD", "params.item_id": item_id}, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(operation.and_then(|operation| {
+            operation
+                .params
+                .get("owner")
+                .and_then(|v| v.as_str().map(ToOwned::to_owned))
+        }))
+    }
+
+    pub async fn backfill_item_sellers(
+        &self,
+        seller: &str,
+        item_ids: &[String],
+    ) -> Result<u64, MongoError> {
+        if item_ids.is_empty() {
+            return Ok(0);
+        }
+
+        let result = self
+            .db
+            .collection::<Item>("items")
+            .update_many(
+                doc! {
+                    "_id": {"$in": item_ids},
+                    "seller": {"$exists": false},
+                    "status": {"$ne": ItemStatus::SOLD.to_string()},
+                },
+                doc! {"$set": {"seller": seller}},
+                None,
+            )
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(result.modified_count)
+    }
+
+    pub async fn has_role(&self, user_id: &str, role: Role) -> Result<bool, MongoError> {
+        let user_roles = self
+            .db
+            .collection::<UserRoles>("user_roles")
+            .find_one(doc! {"_id": user_id.to_lowercase()}, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+
+        Ok(user_roles.is_some_and(|user_roles| user_roles.roles.contains(&role)))
+    }
+
+    pub async fn grant_role(&self, user_id: &str, role: Role) -> Result<(), MongoError> {
+        let update_options = UpdateOptions::builder().upsert(true).build();
+        self.db
+            .collection::<UserRoles>("user_roles")
+            .update_one(
+                doc! {"_id": user_id.to_lowercase()},
+                doc! {
+                    "$addToSet": {"roles": role.to_string()},
+                    "$set": {"updated_at": DateTime::now()},
+                },
+                update_options,
+            )
+            .await
+            .map_err(MongoError::OperationError)?;
+        Ok(())
+    }
+
+    pub async fn is_banned_from_bidding(&self, user_id: &str) -> Result<bool, MongoError> {
+        let ban = self
+            .db
+            .collection::<BiddingBan>("bidding_bans")
+            .find_one(doc! {"_id": user_id.to_lowercase()}, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+        Ok(ban.is_some())
+    }
+
+    /// Records an admin action as attempted. Handlers call this before acting
+    /// and refuse to act when it fails, so no action goes unaudited.
+    pub async fn record_admin_action(
+        &self,
+        actor: &str,
+        action: AdminAction,
+        target: Option<&str>,
+        reason: Option<&str>,
+    ) -> Result<ObjectId, MongoError> {
+        let entry_id = ObjectId::new();
+        let entry = AuditEntry {
+            id: Some(entry_id),
+            actor: actor.to_string(),
+            action,
+            target: target.map(ToOwned::to_owned),
+            reason: reason.map(ToOwned::to_owned),
+            outcome: AuditOutcome::ATTEMPTED,
+            detail: None,
+            created_at: DateTime::now(),
+            finished_at: None,
+        };
+        self.db
+            .collection::<AuditEntry>("admin_audit")
+            .insert_one(&entry, None)
+            .await
+            .map_err(MongoError::OperationError)?;
+        Ok(entry_id)
+    }
+
+    /// Marks an attempted admin action as finished.
+    pub async fn finish_admin_action(
+        &self,
+        entry_id: ObjectId,
+        outcome: AuditOutcome,
+        detail: Option<&str>,
+    ) {
+        if let Err(err) = self
+            .db
+            .collection::<AuditEntry>("admin_audit")
+            .update_one(
+                doc! {"_id": entry_id},
+                doc! {"$set": {
+                    "outcome": outcome.to_string(),
+                    "detail": detail,
+                    "finished_at": DateTime::now(),
+                }},
+                None,
+            )
+            .await
+        {
+            eprintln!(
+                "Failed to record outcome of admin action {}: {:?}",
+                entry_id, err
+            );
+        }
+    }
+
+    /// Rewrites amounts still stored as plain dollar floats as exact money
+    /// documents. Returns how many documents were changed.
+    pub async fn convert_legacy_prices(
+        &self,
+        collection: &str,
+        fields: &[&str],
+    ) -> Result<u64, MongoError> {
+        let collection = self.db.collection::<Document>(collection);
+        let scale = 10_i64.pow(Currency::USD.exponent());
+        let mut converted = 0;
+        for field in fields {
+            let result = collection
+                .update_many(
+                    doc! {*field: {"$type": "number"}},
+                    vec![doc! {"$set": {*field: {
+                        "minor_units": {"$toLong": {"$round": [{"$multiply": [format!("${}", field), scale]}, 0]}},
+                        "currency": Currency::USD.as_str(),
+                    }}}],
+                    None,
+                )
+                .await
+                .map_err(MongoError::OperationError)?;
+            converted += result.modified_count;
+        }
+        Ok(converted)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lot_bid(bidder: &str, quantity: u32, minor_units: i64) -> LotBid {
+        LotBid {
+            id: None,
+            item_id: "42".to_string(),
+            bidder: bidder.to_string(),
+            quantity,
+            bid_price: Money::new(minor_units, Currency::USD),
+            created_at: DateTime::from_millis(0),
+            updated_at: DateTime::from_millis(0),
+        }
+    }
+
+    #[test]
+    fn fills_units_in_ranked_order() {
+        let bids = [
+            lot_bid("alice", 2, 3_000),
+            lot_bid("bob", 3, 2_500),
+            lot_bid("carol", 4, 2_000),
+        ];
+        let allocation = LotAllocation::from_ranked_bids(4, &bids);
+        assert_eq!(
+            allocation.winners,
+            vec![("alice".to_string(), 2), ("bob".to_string(), 2)]
+        );
+        assert_eq!(allocation.units_allocated, 4);
+        assert_eq!(allocation.units_for("bob"), 2);
+        assert_eq!(allocation.units_for("carol"), 0);
+    }
+
+    #[test]
+    fn last_winner_sets_the_clearing_price() {
+        let bids = [lot_bid("alice", 1, 3_000), lot_bid("bob", 1, 2_500)];
+        let allocation = LotAllocation::from_ranked_bids(5, &bids);
+        assert_eq!(allocation.units_allocated, 2);
+        assert_eq!(
+            allocation.clearing_price,
+            Some(Money::new(2_500, Currency::USD))
+        );
+
+        let allocation = LotAllocation::from_ranked_bids(1, &bids);
+        assert_eq!(allocation.winners, vec![("alice".to_string(), 1)]);
+        assert_eq!(
+            allocation.clearing_price,
+            Some(Money::new(3_000, Currency::USD))
+        );
+    }
+
+    #[test]
+    fn audit_entries_from_before_outcomes_count_as_succeeded() {
+        let entry: AuditEntry = bson::from_document(doc! {
+            "actor": "0xadmin",
+            "action": "BAN_BIDDER",
+            "target": "0xbidder",
+            "reason": null,
+            "created_at": DateTime::from_millis(0),
+        })
+        .unwrap();
+        assert_eq!(entry.outcome, AuditOutcome::SUCCEEDED);
+        assert_eq!(entry.detail, None);
+    }
+
+    #[test]
+    fn no_bids_allocate_nothing() {
+        let allocation = LotAllocation::from_ranked_bids(3, &[]);
+        assert!(allocation.winners.is_empty());
+        assert_eq!(allocation.units_allocated, 0);
+        assert_eq!(allocation.clearing_price, None);
+    }
 }
@@ -0,0 +1,264 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::{fmt, path::PathBuf, time::Duration};
+use tokio::io::AsyncWriteExt;
+
+use crate::{mongo::MongoClient, redis::RedisClient, types::CurrentBid};
+
+pub const NOTIFICATION_QUEUE: &str = "notifications";
+
+const MAX_DELIVERY_ATTEMPTS: u32 = 3;
+const IDLE_POLL_SECONDS: u64 = 1;
+const ENDING_SOON_INTERVAL_SECONDS: u64 = 30;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[allow(non_camel_case_types)]
+pub enum NotificationKind {
+    OUTBID,
+    ENDING_SOON,
+    NEW_LISTING,
+}
+
+impl fmt::Display for NotificationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = match self {
+            NotificationKind::OUTBID => "OUTBID",
+            NotificationKind::ENDING_SOON => "ENDING_SOON",
+            NotificationKind::NEW_LISTING => "NEW_LISTING",
+        };
+        write!(f, "{}", kind)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Notification {
+    pub recipient: String,
+    pub kind: NotificationKind,
+    pub item_id: String,
+    pub message: String,
+    pub created_at: String,
+    #[serde(default)]
+    pub attempts: u32,
+}
+
+impl Notification {
+    pub fn new(recipient: &str, kind: NotificationKind, item_id: &str, message: String) -> Self {
+        Notification {
+            recipient: recipient.to_string(),
+            kind,
+            item_id: item_id.to_string(),
+            message,
+            created_at: Utc::now().to_rfc3339(),
+            attempts: 0,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum NotificationError {
+    SerializationError,
+    IoError(std::io::Error),
+}
+
+/// Delivers a single notification to its recipient. Implementations should
+/// return an error for failures worth retrying.
+#[async_trait]
+pub trait NotificationSender: Send + Sync {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError>;
+}
+
+/// Prints notifications to standard output, next to the server log.
+pub struct LogSender;
+
+#[async_trait]
+impl NotificationSender for LogSender {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        println!(
+            "Notification {} for {} on item {}: {}",
+            notification.kind, notification.recipient, notification.item_id, notification.message
+        );
+        Ok(())
+    }
+}
+
+/// Appends notifications to a file, one JSON object per line.
+pub struct FileSender {
+    path: PathBuf,
+}
+
+impl FileSender {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        FileSender { path: path.into() }
+    }
+}
+
+#[async_trait]
+impl NotificationSender for FileSender {
+    async fn send(&self, notification: &Notification) -> Result<(), NotificationError> {
+        let mut line = serde_json::to_string(notification)
+            .map_err(|_| NotificationError::SerializationError)?;
+        line.push('\n');
+
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await
+            .map_err(NotificationError::IoError)?;
+        file.write_all(line.as_bytes())
+            .await
+            .map_err(NotificationError::IoError)
+    }
+}
+
+pub async fn queue_notification(redis_client: &RedisClient, notification: &Notification) {
+    if let Err(err) = redis_client
+        .push_json(NOTIFICATION_QUEUE, notification)
+        .await
+    {
+        eprintln!(
+            "Failed to queue {} notification for {}. Error: {:?}",
+            notification.kind, notification.recipient, err
+        );
+    }
+}
+
+/// Tells the previous leader they lost the lead, unless they just outbid themselves.
+pub async fn queue_outbid_notification(
+    redis_client: &RedisClient,
+    item_id: &str,
+    previous_bid: Option<&CurrentBid>,
+    current_bid: &CurrentBid,
+) {
+    let previous_bidder = match previous_bid {
+        Some(bid) if bid.bidder != current_bid.bidder => &bid.bidder,
+        _ => return,
+    };
+
+    let notification = Notification::new(
+        previous_bidder,
+        NotificationKind::OUTBID,
+        item_id,
+        format!(
+            "You have been outbid, the leading bid is now {} {}",
+            current_bid.bid_price, current_bid.bid_price.currency
+        ),
+    );
+    queue_notification(redis_client, &notification).await;
+}
+
+/// Drains the notification queue through `sender`. Failed deliveries are put
+/// back at the end of the queue until they run out of attempts.
+pub async fn deliver_notifications(redis_client: &RedisClient, sender: &dyn NotificationSender) {
+    loop {
+        let mut notification = match redis_client
+            .pop_json::<Notification>(NOTIFICATION_QUEUE)
+            .await
+        {
+            Ok(Some(notification)) => notification,
+            Ok(None) => {
+                tokio::time::sleep(Duration::from_secs(IDLE_POLL_SECONDS)).await;
+                continue;
+            }
+            Err(err) => {
+                eprintln!("Failed to read notification queue: {:?}", err);
+                tokio::time::sleep(Duration::from_secs(IDLE_POLL_SECONDS)).await;
+                continue;
+            }
+        };
+
+        if let Err(err) = sender.send(&notification).await {
+            notification.attempts += 1;
+            if notification.attempts >= MAX_DELIVERY_ATTEMPTS {
+                eprintln!(
+                    "Dropping {} notification for {} after {} attempts: {:?}",
+                    notification.kind, notification.recipient, notification.attempts, err
+                );
+                continue;
+            }
+            queue_notification(redis_client, &notification).await;
+        }
+    }
+}
+
+/// Periodically notifies everyone who bid on an item once its close is within
+/// `window_seconds`. Each item is only announced once, even if soft close
+/// later pushes its end back.
+pub async fn queue_ending_soon_notifications(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    window_seconds: i64,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(ENDING_SOON_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        let items = match mongo_client
+            .find_items_ending_within(chrono::Duration::seconds(window_seconds))
+            .await
+        {
+            Ok(items) => items,
+            Err(err) => {
+                eprintln!("Failed to find auctions ending soon: {:?}", err);
+                continue;
+            }
+        };
+
+        for item in items {
+            let item_id = match &item.id {
+                Some(item_id) => item_id.clone(),
+                None => continue,
+            };
+
+            let bidders = match mongo_client.find_item_bidders(&item_id).await {
+                Ok(bidders) => bidders,
+                Err(err) => {
+                    eprintln!(
+                        "Failed to find bidders for item id: {}. Error : {:?}",
+                        item_id, err
+                    );
+                    continue;
+                }
+            };
+            if bidders.is_empty() {
+                continue;
+            }
+
+            match redis_client
+                .set_value_if_absent(
+                    "ending_soon_notified",
+                    &item_id,
+                    &item.auction_end.to_chrono().to_rfc3339(),
+                    (window_seconds.max(0) as u64) + 24 * 60 * 60,
+                )
+                .await
+            {
+                Ok(true) => {}
+                Ok(false) => continue,
+                Err(err) => {
+                    eprintln!(
+                        "Failed to mark ending soon notification for item id: {}. Error : {:?}",
+                        item_id, err
+                    );
+                    continue;
+                }
+            }
+
+            for bidder in &bidders {
+                let notification = Notification::new(
+                    bidder,
+                    NotificationKind::ENDING_SOON,
+                    &item_id,
+                    format!(
+                        "The auction for {} ends at {}",
+                        item.title,
+                        item.auction_end.to_chrono().to_rfc3339()
+                    ),
+                );
+                queue_notification(redis_client, &notification).await;
+            }
+        }
+    }
+}
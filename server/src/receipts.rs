@@ -0,0 +1,181 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::SecondsFormat;
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::{money::Money, mongo::Bid};
+
+#[derive(Debug)]
+pub enum ReceiptError {
+    InvalidKey,
+}
+
+/// What the server vouches for when it accepts a bid.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BidReceipt {
+    pub bid_id: String,
+    pub item_id: String,
+    pub bidder: String,
+    pub amount: Money,
+    pub server_timestamp: String,
+}
+
+impl BidReceipt {
+    /// Returns `None` for bids that have not been given an id yet.
+    pub fn from_bid(bid: &Bid) -> Option<Self> {
+        Some(BidReceipt {
+            bid_id: bid.id?.to_hex(),
+            item_id: bid.item_id.clone(),
+            bidder: bid.bidder.clone(),
+            amount: bid.bid_price,
+            server_timestamp: bid
+                .timestamp
+                .to_chrono()
+                .to_rfc3339_opts(SecondsFormat::Millis, true),
+        })
+    }
+
+    /// The exact bytes that are signed, one `field:value` pair per line, so a
+    /// receipt can be checked without agreeing on a JSON encoding.
+    pub fn signing_payload(&self) -> String {
+        format!(
+            "bid-receipt:v1\nbid_id:{}\nitem_id:{}\nbidder:{}\namount:{} {}\nserver_timestamp:{}",
+            self.bid_id,
+            self.item_id,
+            self.bidder,
+            self.amount,
+            self.amount.currency,
+            self.server_timestamp
+        )
+    }
+}
+
+/// A receipt with the server's signature over its `signing_payload`. The
+/// signature is unpadded base64url, like the key published at the JWKS endpoint.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedReceipt {
+    #[serde(flatten)]
+    pub receipt: BidReceipt,
+    pub key_id: String,
+    pub signature: String,
+}
+
+pub struct ReceiptSigner {
+    signing_key: SigningKey,
+    key_id: String,
+}
+
+impl ReceiptSigner {
+    fn from_signing_key(signing_key: SigningKey) -> Self {
+        let digest = Sha256::digest(signing_key.verifying_key().as_bytes());
+        ReceiptSigner {
+            key_id: hex::encode(&digest[..8]),
+            signing_key,
+        }
+    }
+
+    /// Loads the key from its 32 byte seed, hex encoded.
+    pub fn from_seed_hex(seed: &str) -> Result<Self, ReceiptError> {
+        let seed: [u8; 32] = hex::decode(seed.trim())
+            .map_err(|_| ReceiptError::InvalidKey)?
+            .try_into()
+            .map_err(|_| ReceiptError::InvalidKey)?;
+        Ok(Self::from_signing_key(SigningKey::from_bytes(&seed)))
+    }
+
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub fn public_key(&self) -> VerifyingKey {
+        self.signing_key.verifying_key()
+    }
+
+    pub fn sign(&self, receipt: BidReceipt) -> SignedReceipt {
+        let signature = self.signing_key.sign(receipt.signing_payload().as_bytes());
+        SignedReceipt {
+            receipt,
+            key_id: self.key_id.clone(),
+            signature: URL_SAFE_NO_PAD.encode(signature.to_bytes()),
+        }
+    }
+
+    pub fn verify(&self, receipt: &BidReceipt, signature: &str) -> bool {
+        let signature = match URL_SAFE_NO_PAD
+            .decode(signature.trim())
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+        {
+            Some(signature) => signature,
+            None => return false,
+        };
+        self.public_key()
+            .verify(receipt.signing_payload().as_bytes(), &signature)
+            .is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::money::Currency;
+
+    const SEED: &str = "9d61b19deffd5a60ba844af492ec2cc44449c5697b326919703bac031cae7f60";
+
+    fn receipt() -> BidReceipt {
+        BidReceipt {
+            bid_id: "65f0c0ffee0000000000abcd".to_string(),
+            item_id: "42".to_string(),
+            bidder: "0xabc".to_string(),
+            amount: Money::new(1250, Currency::USD),
+            server_timestamp: "2026-01-01T00:00:00.000Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn loads_keys_from_hex_seeds() {
+        let signer = ReceiptSigner::from_seed_hex(SEED).unwrap();
+        let again = ReceiptSigner::from_seed_hex(&format!(" {} ", SEED)).unwrap();
+        assert_eq!(signer.key_id(), again.key_id());
+        assert_eq!(signer.key_id().len(), 16);
+
+        assert!(ReceiptSigner::from_seed_hex("").is_err());
+        assert!(ReceiptSigner::from_seed_hex(&SEED[..62]).is_err());
+        assert!(ReceiptSigner::from_seed_hex(&SEED.replace('9', "z")).is_err());
+    }
+
+    #[test]
+    fn signed_receipts_verify() {
+        let signer = ReceiptSigner::from_seed_hex(SEED).unwrap();
+        let signed = signer.sign(receipt());
+        assert_eq!(signed.key_id, signer.key_id());
+        assert!(signer.verify(&signed.receipt, &signed.signature));
+    }
+
+    #[test]
+    fn tampered_receipts_do_not_verify() {
+        let signer = ReceiptSigner::from_seed_hex(SEED).unwrap();
+        let signed = signer.sign(receipt());
+
+        let mut tampered = receipt();
+        tampered.amount = Money::new(1251, Currency::USD);
+        assert!(!signer.verify(&tampered, &signed.signature));
+
+        let mut tampered = receipt();
+        tampered.bidder = "0xdef".to_string();
+        assert!(!signer.verify(&tampered, &signed.signature));
+
+        assert!(!signer.verify(&receipt(), "not a signature"));
+        assert!(!signer.verify(&receipt(), ""));
+    }
+
+    #[test]
+    fn other_keys_do_not_verify() {
+        let signer = ReceiptSigner::from_seed_hex(SEED).unwrap();
+        let other = ReceiptSigner::from_seed_hex(&"11".repeat(32)).unwrap();
+        let signed = signer.sign(receipt());
+        assert_ne!(signer.key_id(), other.key_id());
+        assert!(!other.verify(&signed.receipt, &signed.signature));
+    }
+}
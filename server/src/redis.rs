@@ -1,14 +1,79 @@
 use core::fmt;
+use std::collections::HashMap;
 
 use bb8_redis::redis::{cmd, AsyncCommands};
 use bb8_redis::{bb8, RedisConnectionManager};
 use serde::{de::DeserializeOwned, Serialize};
 use serde_json::to_string;
 
-use crate::types::{MessageToEnqueue, MessageToPublish};
+use crate::types::MessageToEnqueue;
 
 pub type RedisPool = bb8::Pool<RedisConnectionManager>;
 
+// An empty string stands for a missing key, both as the expected and as the new value.
+const COMPARE_AND_SWAP_SCRIPT: &str = r#"
+local current = redis.call('GET', KEYS[1])
+if current == false then
+    current = ''
+end
+if current ~= ARGV[1] then
+    return 0
+end
+if ARGV[2] == '' then
+    redis.call('DEL', KEYS[1])
+else
+    redis.call('SET', KEYS[1], ARGV[2])
+end
+return 1
+"#;
+
+// Roughly how many bid events are kept per item for reconnecting clients.
+const BID_EVENT_LOG_LENGTH: usize = 1000;
+
+// Sets the value, when ARGV[1] is '1' only if it still holds the expected one,
+// or deletes it when the new value is empty, and in the same step assigns the next sequence number to the event, appends it
+// to the item's capped stream under that sequence and publishes it. Sequences so
+// follow the order in which values were swapped, and the stream and the channel
+// always agree on it. Returns the assigned sequence, or 0 if nothing was set.
+const SWAP_AND_APPEND_EVENT_SCRIPT: &str = r#"
+if ARGV[1] == '1' then
+    local current = redis.call('GET', KEYS[1])
+    if current == false then
+        current = ''
+    end
+    if current ~= ARGV[2] then
+        return 0
+    end
+end
+if ARGV[3] == '' then
+    redis.call('DEL', KEYS[1])
+else
+    redis.call('SET', KEYS[1], ARGV[3])
+end
+local sequence = redis.call('INCR', KEYS[2])
+local event = cjson.decode(ARGV[4])
+event['sequence'] = sequence
+local payload = cjson.encode(event)
+redis.call('XADD', KEYS[3], 'MAXLEN', '~', ARGV[5], sequence .. '-0', 'event', payload)
+redis.call('PUBLISH', ARGV[6], payload)
+return sequence
+"#;
+
+// Returns -1 when the hit fits in the window, otherwise the milliseconds until a slot frees up.
+const SLIDING_WINDOW_SCRIPT: &str = r#"
+local now = tonumber(ARGV[1])
+local window = tonumber(ARGV[2])
+local limit = tonumber(ARGV[3])
+redis.call('ZREMRANGEBYSCORE', KEYS[1], '-inf', now - window)
+if redis.call('ZCARD', KEYS[1]) >= limit then
+    local oldest = redis.call('ZRANGE', KEYS[1], 0, 0, 'WITHSCORES')
+    return tonumber(oldest[2]) + window - now
+end
+redis.call('ZADD', KEYS[1], now, ARGV[4])
+redis.call('PEXPIRE', KEYS[1], window)
+return -1
+"#;
+
 #[derive(Clone)]
 pub struct RedisClient {
     pool: RedisPool,
@@ -63,14 +128,45 @@ impl RedisClient {
             .map_err(RedisClientError::OperationError)
     }
 
-    pub async fn publish<'a>(
+    pub async fn push_json<T: Serialize>(
+        &self,
+        queue_name: &str,
+        message: &T,
+    ) -> Result<(), RedisClientError> {
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+        let serialized_message =
+            to_string(message).map_err(|_| RedisClientError::SerializationError)?;
+        conn.rpush::<_, _, i64>(queue_name, serialized_message)
+            .await
+            .map(|_| ())
+            .map_err(RedisClientError::OperationError)
+    }
+
+    pub async fn pop_json<T: DeserializeOwned>(
+        &self,
+        queue_name: &str,
+    ) -> Result<Option<T>, RedisClientError> {
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+        let value: Option<String> = conn
+            .lpop(queue_name, None)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        value
+            .map(|str_val| {
+                serde_json::from_str(&str_val).map_err(|_| RedisClientError::SerializationError)
+            })
+            .transpose()
+    }
+
+    pub async fn publish<T: Serialize>(
         &self,
-        message: MessageToPublish<'a>,
+        message: &T,
         channel: &str,
     ) -> Result<(), RedisClientError> {
         let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
         let serialized_message =
-            to_string(&message).map_err(|_| return RedisClientError::SerializationError)?;
+            to_string(message).map_err(|_| RedisClientError::SerializationError)?;
         conn.publish::<_, _, i64>(channel, serialized_message)
             .await
             .map(|_| ())
@@ -149,6 +245,56 @@ impl RedisClient {
             .transpose()
     }
 
+    /// Reads and deletes the value in one step, so only one caller can ever take it.
+    pub async fn take_value<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        key: &str,
+    ) -> Result<Option<T>, RedisClientError> {
+        let prefixed_key = Self::create_prefixed_key(prefix, key);
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let value: Option<String> = cmd("GETDEL")
+            .arg(prefixed_key)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        value
+            .map(|str_val| {
+                serde_json::from_str(&str_val).map_err(|_| RedisClientError::SerializationError)
+            })
+            .transpose()
+    }
+
+    pub async fn get_values<T: DeserializeOwned>(
+        &self,
+        prefix: &str,
+        keys: &[String],
+    ) -> Result<Vec<Option<T>>, RedisClientError> {
+        if keys.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let prefixed_keys: Vec<String> = keys
+            .iter()
+            .map(|key| Self::create_prefixed_key(prefix, key))
+            .collect();
+
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let values: Vec<Option<String>> = cmd("MGET")
+            .arg(&prefixed_keys)
+            .query_async::<_, Vec<Option<String>>>(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok(values
+            .into_iter()
+            .map(|value| value.and_then(|str_val| serde_json::from_str(&str_val).ok()))
+            .collect())
+    }
+
     pub async fn set_value<T: Serialize>(
         &self,
         prefix: &str,
@@ -178,6 +324,244 @@ impl RedisClient {
         Ok(())
     }
 
+    /// Stores the value only if the key does not exist yet. Returns whether it was stored.
+    pub async fn set_value_if_absent<T: Serialize>(
+        &self,
+        prefix: &str,
+        key: &str,
+        value: &T,
+        ttl_seconds: u64,
+    ) -> Result<bool, RedisClientError> {
+        let prefixed_key = Self::create_prefixed_key(prefix, key);
+        let serialized_value =
+            serde_json::to_string(value).map_err(|_| RedisClientError::SerializationError)?;
+
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let stored: Option<String> = cmd("SET")
+            .arg(prefixed_key)
+            .arg(serialized_value)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl_seconds)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok(stored.is_some())
+    }
+
+    pub async fn compare_and_swap<T: Serialize>(
+        &self,
+        prefix: &str,
+        key: &str,
+        expected: Option<&T>,
+        new_value: Option<&T>,
+    ) -> Result<bool, RedisClientError> {
+        let prefixed_key = Self::create_prefixed_key(prefix, key);
+        let serialize = |value: Option<&T>| -> Result<String, RedisClientError> {
+            match value {
+                Some(value) => {
+                    serde_json::to_string(value).map_err(|_| RedisClientError::SerializationError)
+                }
+                None => Ok(String::new()),
+            }
+        };
+        let serialized_expected = serialize(expected)?;
+        let serialized_new = serialize(new_value)?;
+
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let swapped: i64 = cmd("EVAL")
+            .arg(COMPARE_AND_SWAP_SCRIPT)
+            .arg(1)
+            .arg(prefixed_key)
+            .arg(serialized_expected)
+            .arg(serialized_new)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok(swapped == 1)
+    }
+
+    pub async fn sliding_window_hit(
+        &self,
+        key: &str,
+        window_ms: u64,
+        limit: u64,
+    ) -> Result<Option<u64>, RedisClientError> {
+        let now_ms = chrono::Utc::now().timestamp_millis();
+        let member = format!("{}-{}", now_ms, uuid::Uuid::new_v4());
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let retry_after_ms: i64 = cmd("EVAL")
+            .arg(SLIDING_WINDOW_SCRIPT)
+            .arg(1)
+            .arg(key)
+            .arg(now_ms)
+            .arg(window_ms)
+            .arg(limit)
+            .arg(member)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok((retry_after_ms >= 0).then_some(retry_after_ms as u64))
+    }
+
+    pub async fn increment_hash_counter(
+        &self,
+        key: &str,
+        field: &str,
+    ) -> Result<i64, RedisClientError> {
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        conn.hincr(key, field, 1)
+            .await
+            .map_err(RedisClientError::OperationError)
+    }
+
+    async fn swap_and_append_event<E: Serialize>(
+        &self,
+        prefixed_key: String,
+        expected: Option<String>,
+        new_value: String,
+        item_id: &str,
+        event: &E,
+    ) -> Result<Option<u64>, RedisClientError> {
+        let serialized_event =
+            to_string(event).map_err(|_| RedisClientError::SerializationError)?;
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let sequence: u64 = cmd("EVAL")
+            .arg(SWAP_AND_APPEND_EVENT_SCRIPT)
+            .arg(3)
+            .arg(prefixed_key)
+            .arg(format!("bid_event_seq:{}", item_id))
+            .arg(format!("bid_events:{}", item_id))
+            .arg(if expected.is_some() { "1" } else { "0" })
+            .arg(expected.unwrap_or_default())
+            .arg(new_value)
+            .arg(serialized_event)
+            .arg(BID_EVENT_LOG_LENGTH)
+            .arg(item_id)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok((sequence > 0).then_some(sequence))
+    }
+
+    /// Like `compare_and_swap`, but a successful swap also appends `event` to
+    /// the item's event log. Returns the event's sequence, or `None` when the
+    /// value no longer matched `expected`.
+    pub async fn compare_and_swap_with_event<T: Serialize, E: Serialize>(
+        &self,
+        prefix: &str,
+        key: &str,
+        expected: Option<&T>,
+        new_value: Option<&T>,
+        item_id: &str,
+        event: &E,
+    ) -> Result<Option<u64>, RedisClientError> {
+        let serialize = |value: Option<&T>| -> Result<String, RedisClientError> {
+            match value {
+                Some(value) => to_string(value).map_err(|_| RedisClientError::SerializationError),
+                None => Ok(String::new()),
+            }
+        };
+        let serialized_expected = serialize(expected)?;
+        let serialized_new = serialize(new_value)?;
+
+        self.swap_and_append_event(
+            Self::create_prefixed_key(prefix, key),
+            Some(serialized_expected),
+            serialized_new,
+            item_id,
+            event,
+        )
+        .await
+    }
+
+    /// Sets the value and appends `event` to the item's event log in one step.
+    pub async fn set_value_with_event<T: Serialize, E: Serialize>(
+        &self,
+        prefix: &str,
+        key: &str,
+        value: &T,
+        item_id: &str,
+        event: &E,
+    ) -> Result<u64, RedisClientError> {
+        let serialized_value =
+            to_string(value).map_err(|_| RedisClientError::SerializationError)?;
+
+        self.swap_and_append_event(
+            Self::create_prefixed_key(prefix, key),
+            None,
+            serialized_value,
+            item_id,
+            event,
+        )
+        .await
+        .map(Option::unwrap_or_default)
+    }
+
+    pub async fn read_events(
+        &self,
+        item_id: &str,
+        since: u64,
+        count: usize,
+    ) -> Result<Vec<(u64, String)>, RedisClientError> {
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        let entries: Vec<(String, HashMap<String, String>)> = cmd("XRANGE")
+            .arg(format!("bid_events:{}", item_id))
+            .arg(format!("{}-0", since + 1))
+            .arg("+")
+            .arg("COUNT")
+            .arg(count)
+            .query_async(&mut *conn)
+            .await
+            .map_err(RedisClientError::OperationError)?;
+
+        Ok(entries
+            .into_iter()
+            .filter_map(|(id, mut fields)| {
+                let sequence = id.split('-').next()?.parse().ok()?;
+                Some((sequence, fields.remove("event")?))
+            })
+            .collect())
+    }
+
+    /// Returns the unprefixed keys of every value stored under `prefix`.
+    pub async fn scan_keys(&self, prefix: &str) -> Result<Vec<String>, RedisClientError> {
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+        let pattern = Self::create_prefixed_key(prefix, "*");
+
+        let mut keys = vec![];
+        let mut cursor: u64 = 0;
+        loop {
+            let (next_cursor, batch): (u64, Vec<String>) = cmd("SCAN")
+                .arg(cursor)
+                .arg("MATCH")
+                .arg(&pattern)
+                .arg("COUNT")
+                .arg(100)
+                .query_async(&mut *conn)
+                .await
+                .map_err(RedisClientError::OperationError)?;
+            keys.extend(batch.into_iter().filter_map(|key| {
+                key.strip_prefix(&format!("{}:", prefix))
+                    .map(ToOwned::to_owned)
+            }));
+            if next_cursor == 0 {
+                return Ok(keys);
+            }
+            cursor = next_cursor;
+        }
+    }
+
     pub async fn delete_key(&self, key: &str) -> Result<(), RedisClientError> {
         let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
 
@@ -246,6 +630,22 @@ impl RedisClient {
             .collect()
     }
 
+    pub async fn add_item_to_user_list<T: Serialize>(
+        &self,
+        user_id: &str,
+        item: &T,
+    ) -> Result<(), RedisClientError> {
+        let key = format!("transferring_items:{}", user_id);
+        let serialized_item =
+            serde_json::to_string(item).map_err(|_| RedisClientError::SerializationError)?;
+        let mut conn = self.pool.get().await.map_err(RedisClientError::PoolError)?;
+
+        conn.rpush::<_, _, i64>(&key, serialized_item)
+            .await
+            .map(|_| ())
+            .map_err(RedisClientError::OperationError)
+    }
+
     pub async fn remove_items_with_id_from_user_list(
         &self,
         user_id: &str,
@@ -0,0 +1,58 @@
+use std::time::Duration;
+
+use crate::{
+    elasticsearch::ElasticSearchClient, handlers::alert_saved_searches, mongo::MongoClient,
+    redis::RedisClient,
+};
+
+const ACTIVATION_INTERVAL_SECONDS: u64 = 30;
+
+/// Promotes SCHEDULED items to ACTIVE once their start time has passed. Bidding
+/// and listings already treat started items as open, so this only needs to
+/// keep the stored status in step. Saved searches are alerted here too, for
+/// these items and for those the rating service made ACTIVE straight away.
+pub async fn activate_scheduled_items(
+    mongo_client: &MongoClient,
+    redis_client: &RedisClient,
+    elasticsearch_client: &ElasticSearchClient,
+) {
+    let mut interval = tokio::time::interval(Duration::from_secs(ACTIVATION_INTERVAL_SECONDS));
+
+    loop {
+        interval.tick().await;
+
+        let item_ids = match mongo_client.activate_scheduled_items().await {
+            Ok(item_ids) => item_ids,
+            Err(err) => {
+                eprintln!("Failed to activate scheduled items: {:?}", err);
+                vec![]
+            }
+        };
+
+        for item_id in &item_ids {
+            if let Err(err) = redis_client
+                .delete_key(&format!("item_details:{}", item_id))
+                .await
+            {
+                eprintln!(
+                    "Failed to delete item details for item id: {}. Error : {:?}",
+                    item_id, err
+                );
+            }
+        }
+
+        if !item_ids.is_empty() {
+            println!("Activated {} scheduled items", item_ids.len());
+        }
+
+        match mongo_client.claim_items_awaiting_search_alerts().await {
+            Ok(items) => {
+                for item in &items {
+                    alert_saved_searches(mongo_client, redis_client, elasticsearch_client, item)
+                        .await;
+                }
+            }
+            Err(err) => eprintln!("Failed to fetch items awaiting search alerts: {:?}", err),
+        }
+    }
+}
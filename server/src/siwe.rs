@@ -0,0 +1,291 @@
+use chrono::{DateTime, Utc};
+use k256::ecdsa::{RecoveryId, Signature, VerifyingKey};
+use tiny_keccak::{Hasher, Keccak};
+
+/// The fields of an EIP-4361 (Sign-In with Ethereum) message that the server
+/// checks. Optional fields it does not use, such as resources, are ignored.
+#[derive(Debug)]
+pub struct SiweMessage {
+    pub domain: String,
+    pub address: String,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum SiweError {
+    MalformedMessage(&'static str),
+    InvalidSignature,
+    AddressMismatch,
+}
+
+fn parse_time(value: &str) -> Result<DateTime<Utc>, SiweError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| SiweError::MalformedMessage("invalid timestamp"))
+}
+
+impl SiweMessage {
+    pub fn parse(message: &str) -> Result<Self, SiweError> {
+        let mut lines = message.lines();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(" wants you to sign in with your Ethereum account:"))
+            .ok_or(SiweError::MalformedMessage("missing domain line"))?
+            .to_string();
+        let address = lines
+            .next()
+            .filter(|address| is_address(address))
+            .ok_or(SiweError::MalformedMessage("missing address"))?
+            .to_string();
+
+        let mut uri = None;
+        let mut version = None;
+        let mut chain_id = None;
+        let mut nonce = None;
+        let mut issued_at = None;
+        let mut expiration_time = None;
+        let mut not_before = None;
+
+        // The statement between the address and the fields is free text.
+        for line in lines {
+            if let Some(value) = line.strip_prefix("URI: ") {
+                uri = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix("Version: ") {
+                version = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix("Chain ID: ") {
+                chain_id = Some(
+                    value
+                        .parse()
+                        .map_err(|_| SiweError::MalformedMessage("invalid chain id"))?,
+                );
+            } else if let Some(value) = line.strip_prefix("Nonce: ") {
+                nonce = Some(value.to_string());
+            } else if let Some(value) = line.strip_prefix("Issued At: ") {
+                issued_at = Some(parse_time(value)?);
+            } else if let Some(value) = line.strip_prefix("Expiration Time: ") {
+                expiration_time = Some(parse_time(value)?);
+            } else if let Some(value) = line.strip_prefix("Not Before: ") {
+                not_before = Some(parse_time(value)?);
+            }
+        }
+
+        Ok(SiweMessage {
+            domain,
+            address,
+            uri: uri.ok_or(SiweError::MalformedMessage("missing URI"))?,
+            version: version.ok_or(SiweError::MalformedMessage("missing version"))?,
+            chain_id: chain_id.ok_or(SiweError::MalformedMessage("missing chain id"))?,
+            nonce: nonce.ok_or(SiweError::MalformedMessage("missing nonce"))?,
+            issued_at: issued_at.ok_or(SiweError::MalformedMessage("missing issued at"))?,
+            expiration_time,
+            not_before,
+        })
+    }
+
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.expiration_time
+            .is_none_or(|expiration| now < expiration)
+            && self.not_before.is_none_or(|not_before| now >= not_before)
+    }
+}
+
+fn is_address(value: &str) -> bool {
+    value
+        .strip_prefix("0x")
+        .is_some_and(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    let mut hasher = Keccak::v256();
+    let mut output = [0u8; 32];
+    hasher.update(data);
+    hasher.finalize(&mut output);
+    output
+}
+
+/// The EIP-191 `personal_sign` digest of a message.
+pub fn personal_message_hash(message: &str) -> [u8; 32] {
+    let mut data = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+    data.extend_from_slice(message.as_bytes());
+    keccak256(&data)
+}
+
+/// Recovers the lowercase `0x` address that produced a 65 byte `personal_sign`
+/// signature over `message`. Works offline, no chain access is needed.
+pub fn recover_address(message: &str, signature: &str) -> Result<String, SiweError> {
+    let bytes = hex::decode(signature.trim().trim_start_matches("0x"))
+        .map_err(|_| SiweError::InvalidSignature)?;
+    if bytes.len() != 65 {
+        return Err(SiweError::InvalidSignature);
+    }
+
+    let mut signature =
+        Signature::from_slice(&bytes[..64]).map_err(|_| SiweError::InvalidSignature)?;
+    // Wallets use either 27/28 or 0/1 for the recovery byte.
+    let mut recovery_byte = match bytes[64] {
+        27 | 28 => bytes[64] - 27,
+        0 | 1 => bytes[64],
+        _ => return Err(SiweError::InvalidSignature),
+    };
+    if let Some(normalized) = signature.normalize_s() {
+        signature = normalized;
+        recovery_byte ^= 1;
+    }
+    let recovery_id = RecoveryId::from_byte(recovery_byte).ok_or(SiweError::InvalidSignature)?;
+
+    let verifying_key = VerifyingKey::recover_from_prehash(
+        &personal_message_hash(message),
+        &signature,
+        recovery_id,
+    )
+    .map_err(|_| SiweError::InvalidSignature)?;
+
+    let public_key = verifying_key.to_encoded_point(false);
+    let hash = keccak256(&public_key.as_bytes()[1..]);
+    Ok(format!("0x{}", hex::encode(&hash[12..])))
+}
+
+/// Checks that `signature` over `message` was made by the address the message names.
+pub fn verify(message: &str, signature: &str) -> Result<SiweMessage, SiweError> {
+    let parsed = SiweMessage::parse(message)?;
+    let signer = recover_address(message, signature)?;
+    if !signer.eq_ignore_ascii_case(&parsed.address) {
+        return Err(SiweError::AddressMismatch);
+    }
+    Ok(parsed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+
+    const MESSAGE: &str = "example.com wants you to sign in with your Ethereum account:
+0x0000000000000000000000000000000000000000
+
+Sign in to SmartBid.
+
+URI: https://example.com
+Version: 1
+Chain ID: 80002
+Nonce: 0123456789abcdef
+Issued At: 2026-01-01T00:00:00Z
+Expiration Time: 2026-01-01T00:10:00Z";
+
+    fn signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32].into()).unwrap()
+    }
+
+    fn address_of(key: &SigningKey) -> String {
+        let public_key = key.verifying_key().to_encoded_point(false);
+        let hash = keccak256(&public_key.as_bytes()[1..]);
+        format!("0x{}", hex::encode(&hash[12..]))
+    }
+
+    fn sign(key: &SigningKey, message: &str, recovery_offset: u8) -> (Signature, u8, String) {
+        let (signature, recovery_id) = key
+            .sign_prehash_recoverable(&personal_message_hash(message))
+            .unwrap();
+        let mut bytes = signature.to_bytes().to_vec();
+        bytes.push(recovery_id.to_byte() + recovery_offset);
+        (signature, recovery_id.to_byte(), hex::encode(bytes))
+    }
+
+    fn message_for(address: &str) -> String {
+        MESSAGE.replace("0x0000000000000000000000000000000000000000", address)
+    }
+
+    #[test]
+    fn recovers_the_signer_with_27_28_recovery_ids() {
+        let key = signing_key();
+        let (_, _, signature) = sign(&key, MESSAGE, 27);
+        assert_eq!(
+            recover_address(MESSAGE, &format!("0x{}", signature)),
+            Ok(address_of(&key))
+        );
+    }
+
+    #[test]
+    fn recovers_the_signer_with_0_1_recovery_ids() {
+        let key = signing_key();
+        let (_, _, signature) = sign(&key, MESSAGE, 0);
+        assert_eq!(recover_address(MESSAGE, &signature), Ok(address_of(&key)));
+    }
+
+    #[test]
+    fn normalizes_high_s_signatures() {
+        let key = signing_key();
+        let (signature, recovery_byte, _) = sign(&key, MESSAGE, 0);
+        let (r, s) = signature.split_scalars();
+        let high_s = Signature::from_scalars(r, -s).unwrap();
+        assert!(high_s.normalize_s().is_some());
+
+        let mut bytes = high_s.to_bytes().to_vec();
+        bytes.push((recovery_byte ^ 1) + 27);
+        assert_eq!(
+            recover_address(MESSAGE, &hex::encode(bytes)),
+            Ok(address_of(&key))
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_signatures() {
+        let key = signing_key();
+        let (_, _, signature) = sign(&key, MESSAGE, 27);
+        let mut bytes = hex::decode(&signature).unwrap();
+
+        assert_eq!(
+            recover_address(MESSAGE, &hex::encode(&bytes[..64])),
+            Err(SiweError::InvalidSignature)
+        );
+        bytes[64] = 29;
+        assert_eq!(
+            recover_address(MESSAGE, &hex::encode(&bytes)),
+            Err(SiweError::InvalidSignature)
+        );
+        assert_eq!(
+            recover_address(MESSAGE, "not hex"),
+            Err(SiweError::InvalidSignature)
+        );
+    }
+
+    #[test]
+    fn verify_checks_the_message_address() {
+        let key = signing_key();
+        let message = message_for(&address_of(&key).to_uppercase().replacen("0X", "0x", 1));
+        let (_, _, signature) = sign(&key, &message, 27);
+        let parsed = verify(&message, &signature).unwrap();
+        assert_eq!(parsed.chain_id, 80002);
+        assert_eq!(parsed.nonce, "0123456789abcdef");
+
+        let (_, _, signature) = sign(&key, MESSAGE, 27);
+        assert_eq!(
+            verify(MESSAGE, &signature).unwrap_err(),
+            SiweError::AddressMismatch
+        );
+    }
+
+    #[test]
+    fn checks_the_validity_window() {
+        let message = SiweMessage::parse(MESSAGE).unwrap();
+        let at = |time: &str| parse_time(time).unwrap();
+        assert!(message.is_valid_at(at("2026-01-01T00:05:00Z")));
+        assert!(!message.is_valid_at(at("2026-01-01T00:10:00Z")));
+    }
+
+    #[test]
+    fn rejects_messages_missing_fields() {
+        let message = MESSAGE.replace("Nonce: 0123456789abcdef\n", "");
+        assert_eq!(
+            SiweMessage::parse(&message).unwrap_err(),
+            SiweError::MalformedMessage("missing nonce")
+        );
+    }
+}
@@ -1,14 +1,41 @@
+use chrono::{DateTime, Duration, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-#[derive(Serialize)]
-pub struct MessageToPublish<'a> {
-    pub price: &'a str,
+use crate::money::{Currency, Money};
+
+pub const BID_EVENT_VERSION: u32 = 3;
+
+/// Why an item's leading bid changed.
+#[derive(Debug, Clone, Copy, Serialize, PartialEq)]
+pub enum BidEventKind {
+    /// A new bid took the lead.
+    BID,
+    /// The leading bid was withdrawn because it could not be recorded. The
+    /// event carries the bid that leads again, or the starting price when no
+    /// bid is left.
+    RETRACTED,
+    /// The bid won the auction outright, through buy now or by accepting a
+    /// Dutch auction's price.
+    SOLD,
 }
 
-impl<'a> MessageToPublish<'a> {
-    pub fn new(price: &'a str) -> Self {
-        MessageToPublish { price }
-    }
+/// Payload published on an item's channel whenever its leading bid changes.
+/// Version 1 only carried `price` and `auction_end`; both keep their original
+/// shape so older subscribers continue to work. `price` is the exact decimal
+/// amount in `currency`. Version 3 added `kind`.
+#[derive(Serialize)]
+pub struct BidEvent<'a> {
+    pub version: u32,
+    pub kind: BidEventKind,
+    pub item_id: &'a str,
+    pub price: String,
+    pub currency: Currency,
+    pub bidder_alias: String,
+    pub bid_count: u64,
+    pub sequence: u64,
+    pub server_timestamp: String,
+    pub auction_end: &'a str,
 }
 
 #[derive(Serialize)]
@@ -32,8 +59,277 @@ pub struct TransferSchedulerURI {
     pub uri: String,
 }
 
+#[derive(Debug, Clone)]
+pub struct SoftCloseConfig {
+    pub window_seconds: i64,
+    pub extension_seconds: i64,
+}
+
+impl SoftCloseConfig {
+    pub fn extended_auction_end(
+        &self,
+        auction_end: DateTime<Utc>,
+        now: DateTime<Utc>,
+    ) -> Option<DateTime<Utc>> {
+        if auction_end - now > Duration::seconds(self.window_seconds) {
+            return None;
+        }
+        let extended_end = now + Duration::seconds(self.extension_seconds);
+        (extended_end > auction_end).then_some(extended_end)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BuyNowConfig {
+    pub disable_fraction: f64,
+}
+
+impl BuyNowConfig {
+    pub fn is_available(&self, buy_now_price: Money, current_bid_price: Option<Money>) -> bool {
+        current_bid_price.is_none_or(|price| {
+            (price.minor_units as f64) < buy_now_price.minor_units as f64 * self.disable_fraction
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BidRateLimitConfig {
+    pub window_seconds: u64,
+    pub bidder_limit: u64,
+    pub item_limit: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct IdempotencyConfig {
+    pub ttl_seconds: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct AuthConfig {
+    /// The domain and chain sign-in messages must name.
+    pub siwe_domain: String,
+    pub siwe_chain_id: u64,
+    pub nonce_ttl_seconds: u64,
+    pub session_ttl_seconds: u64,
+}
+
+#[derive(Debug, Clone)]
+pub struct SignedBidConfig {
+    /// Amount in the item's major currency units from which bids must carry a
+    /// wallet signature. `None` leaves signatures optional for every bid.
+    pub threshold: Option<f64>,
+    pub max_validity_seconds: i64,
+}
+
+impl SignedBidConfig {
+    pub fn is_required(&self, amount: Money) -> bool {
+        self.threshold
+            .and_then(|threshold| Money::from_major(threshold, amount.currency))
+            .is_some_and(|threshold| amount >= threshold)
+    }
+
+    /// The highest amount an unsigned proxy bid may still reach on its own.
+    pub fn unsigned_limit(&self, currency: Currency) -> Option<Money> {
+        self.threshold
+            .and_then(|threshold| Money::from_major(threshold, currency))
+            .and_then(|threshold| threshold.checked_sub(Money::new(1, currency)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct BiddingConfig {
+    pub increments: BidIncrementRules,
+    pub soft_close: SoftCloseConfig,
+    pub buy_now: BuyNowConfig,
+    pub rate_limit: BidRateLimitConfig,
+    pub signed_bids: SignedBidConfig,
+}
+
 #[derive(Serialize, Deserialize)]
 pub struct CurrentBid {
-    pub bid_price: f64,
+    pub bid_price: Money,
     pub bidder: String,
 }
+
+/// Increment bands are configured in major units of whatever currency the item
+/// is priced in.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IncrementBand {
+    pub below: Option<f64>,
+    pub min_increment: f64,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BidIncrementRules {
+    pub default: Vec<IncrementBand>,
+    #[serde(default)]
+    pub categories: HashMap<String, Vec<IncrementBand>>,
+}
+
+impl Default for BidIncrementRules {
+    fn default() -> Self {
+        BidIncrementRules {
+            default: vec![
+                IncrementBand {
+                    below: Some(100.0),
+                    min_increment: 1.0,
+                },
+                IncrementBand {
+                    below: Some(1000.0),
+                    min_increment: 10.0,
+                },
+                IncrementBand {
+                    below: None,
+                    min_increment: 50.0,
+                },
+            ],
+            categories: HashMap::new(),
+        }
+    }
+}
+
+impl BidIncrementRules {
+    pub fn from_json(rules: &str) -> Result<Self, serde_json::Error> {
+        let mut rules: BidIncrementRules = serde_json::from_str(rules)?;
+        rules.categories = rules
+            .categories
+            .into_iter()
+            .map(|(category, bands)| (category.to_lowercase(), bands))
+            .collect();
+        Ok(rules)
+    }
+
+    pub fn min_increment(&self, category: &str, price: Money) -> Money {
+        let bands = self
+            .categories
+            .get(&category.to_lowercase())
+            .unwrap_or(&self.default);
+
+        bands
+            .iter()
+            .find(|band| {
+                band.below.is_none_or(|below| {
+                    Money::from_major(below, price.currency).is_none_or(|below| price < below)
+                })
+            })
+            .and_then(|band| Money::from_major(band.min_increment, price.currency))
+            .unwrap_or(Money::zero(price.currency))
+    }
+
+    pub fn next_bid_price(
+        &self,
+        category: &str,
+        current_bid_price: Option<Money>,
+        base_price: Money,
+    ) -> Money {
+        match current_bid_price {
+            Some(price) => price
+                .checked_add(self.min_increment(category, price))
+                .unwrap_or(price),
+            None => base_price,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn usd(minor_units: i64) -> Money {
+        Money::new(minor_units, Currency::USD)
+    }
+
+    #[test]
+    fn default_bands_switch_at_their_upper_bound() {
+        let rules = BidIncrementRules::default();
+        assert_eq!(rules.min_increment("art", usd(0)), usd(100));
+        assert_eq!(rules.min_increment("art", usd(9_999)), usd(100));
+        assert_eq!(rules.min_increment("art", usd(10_000)), usd(1_000));
+        assert_eq!(rules.min_increment("art", usd(99_999)), usd(1_000));
+        assert_eq!(rules.min_increment("art", usd(100_000)), usd(5_000));
+        assert_eq!(rules.min_increment("art", usd(i64::MAX)), usd(5_000));
+    }
+
+    #[test]
+    fn bands_are_in_the_item_currency() {
+        let rules = BidIncrementRules::default();
+        let jpy = Currency::parse("JPY").unwrap();
+        assert_eq!(
+            rules.min_increment("art", Money::new(99, jpy)),
+            Money::new(1, jpy)
+        );
+        assert_eq!(
+            rules.min_increment("art", Money::new(100, jpy)),
+            Money::new(10, jpy)
+        );
+    }
+
+    #[test]
+    fn category_bands_override_the_default() {
+        let rules = BidIncrementRules::from_json(
+            r#"{
+                "default": [{"below": null, "min_increment": 1}],
+                "categories": {"Watches": [
+                    {"below": 500, "min_increment": 5},
+                    {"below": null, "min_increment": 25}
+                ]}
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(rules.min_increment("WATCHES", usd(49_999)), usd(500));
+        assert_eq!(rules.min_increment("watches", usd(50_000)), usd(2_500));
+        assert_eq!(rules.min_increment("books", usd(50_000)), usd(100));
+    }
+
+    #[test]
+    fn next_bid_price_starts_at_the_base_price() {
+        let rules = BidIncrementRules::default();
+        assert_eq!(rules.next_bid_price("art", None, usd(2_500)), usd(2_500));
+        assert_eq!(
+            rules.next_bid_price("art", Some(usd(9_950)), usd(2_500)),
+            usd(10_050)
+        );
+        assert_eq!(
+            rules.next_bid_price("art", Some(usd(10_000)), usd(2_500)),
+            usd(11_000)
+        );
+    }
+
+    #[test]
+    fn bid_events_name_their_kind() {
+        let event = BidEvent {
+            version: BID_EVENT_VERSION,
+            kind: BidEventKind::RETRACTED,
+            item_id: "42",
+            price: "25.00".to_string(),
+            currency: Currency::USD,
+            bidder_alias: String::new(),
+            bid_count: 0,
+            sequence: 0,
+            server_timestamp: "2026-01-01T00:00:00+00:00".to_string(),
+            auction_end: "2026-01-02T00:00:00+00:00",
+        };
+        let value = serde_json::to_value(&event).unwrap();
+        assert_eq!(value["kind"], "RETRACTED");
+        assert_eq!(value["price"], "25.00");
+        assert_eq!(value["version"], 3);
+    }
+
+    #[test]
+    fn signed_bid_threshold_is_inclusive() {
+        let config = SignedBidConfig {
+            threshold: Some(100.0),
+            max_validity_seconds: 900,
+        };
+        assert!(!config.is_required(usd(9_999)));
+        assert!(config.is_required(usd(10_000)));
+        assert_eq!(config.unsigned_limit(Currency::USD), Some(usd(9_999)));
+
+        let optional = SignedBidConfig {
+            threshold: None,
+            ..config
+        };
+        assert!(!optional.is_required(usd(i64::MAX)));
+        assert_eq!(optional.unsigned_limit(Currency::USD), None);
+    }
+}
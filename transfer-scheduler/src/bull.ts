@@ -14,9 +14,32 @@ const JOBS = {
   transferJob: "transfer-job",
 };
 
+const auctionCloseJobId = (itemId: string) => `auction-close-${itemId}`;
+
 export async function addJob(data: transferQueueElement, delay: number): Promise<void> {
   console.log("Job with ", data, " being scheduled with delay of ", delay, " milliseconds");
-  await queue.add(JOBS.transferJob, data, { delay });
+  const jobId = data?.type == 1 ? auctionCloseJobId(data.item_id) : undefined;
+  await queue.add(JOBS.transferJob, data, { delay, jobId });
+}
+
+export async function rescheduleAuctionClose(itemId: string, delay: number): Promise<boolean> {
+  const job = await queue.getJob(auctionCloseJobId(itemId));
+  if (!job || !(await job.isDelayed())) {
+    return false;
+  }
+  console.log("Auction close for item ", itemId, " being rescheduled with delay of ", delay, " milliseconds");
+  await job.changeDelay(delay);
+  return true;
+}
+
+export async function cancelAuctionClose(itemId: string): Promise<boolean> {
+  const job = await queue.getJob(auctionCloseJobId(itemId));
+  if (!job || !(await job.isDelayed())) {
+    return false;
+  }
+  console.log("Auction close for item ", itemId, " being cancelled");
+  await job.remove();
+  return true;
 }
 
 const callback = async (job: Job) => {
@@ -1,5 +1,5 @@
 import express from "express";
-import { addJob } from "./bull";
+import { addJob, cancelAuctionClose, rescheduleAuctionClose } from "./bull";
 import { schedulerPort } from "./config";
 const app = express();
 app.use(express.json());
@@ -22,6 +22,38 @@ app.post("/", async (req, res) => {
   }
 });
 
+app.post("/reschedule", async (req, res) => {
+  try {
+    const { item_id, delay } = req.body;
+    if (typeof item_id != "string" || typeof delay != "number") {
+      res.json({ status: "error", message: "Invalid or missing item_id or delay" });
+    } else if (await rescheduleAuctionClose(item_id, delay)) {
+      res.json({ status: "success", message: "Job rescheduled successfully" });
+    } else {
+      res.json({ status: "error", message: "No pending auction close job for item" });
+    }
+  } catch (err) {
+    console.log("Error occured:", err);
+    res.json({ status: "error", message: "Internal server error" });
+  }
+});
+
+app.post("/cancel", async (req, res) => {
+  try {
+    const { item_id } = req.body;
+    if (typeof item_id != "string") {
+      res.json({ status: "error", message: "Invalid or missing item_id" });
+    } else if (await cancelAuctionClose(item_id)) {
+      res.json({ status: "success", message: "Job cancelled successfully" });
+    } else {
+      res.json({ status: "error", message: "No pending auction close job for item" });
+    }
+  } catch (err) {
+    console.log("Error occured:", err);
+    res.json({ status: "error", message: "Internal server error" });
+  }
+});
+
 app.listen(port, () => {
   console.log("Server listening on port", port);
 });
@@ -13,6 +13,7 @@ export type transferQueueElement =
       item_id: string;
       user_id: string;
       price: string;
+      currency: string;
       prev_user_id: string;
     }
   | {
@@ -1,30 +1,58 @@
 import mongoose, { Schema, Document, Model } from "mongoose";
 import { mongoConfig } from "./config";
-import { Bid, Item } from "./types";
+import { Bid, Item, LotBid, LotWinner, Money, RankedBid } from "./types";
 import { invalidateItemDetails } from "./redis";
 import { removeItemFromElasticsearch } from "./elasticsearch";
+import { maxMoney } from "./money";
+
+const moneySchema = new Schema<Money>(
+  {
+    minor_units: { type: Number, required: true },
+    currency: { type: String, required: true },
+  },
+  { _id: false }
+);
 
 const bidSchema = new Schema<Bid>({
-  bid_price: { type: Number, required: true },
+  bid_price: { type: moneySchema, required: true },
   bidder: { type: String, required: true },
   item_id: { type: String, ref: "Item", required: true },
   timestamp: { type: Date, default: Date.now },
 });
 
+const lotBidSchema = new Schema<LotBid>(
+  {
+    item_id: { type: String, ref: "Item", required: true },
+    bidder: { type: String, required: true },
+    quantity: { type: Number, required: true },
+    bid_price: { type: moneySchema, required: true },
+    created_at: { type: Date, default: Date.now },
+    updated_at: { type: Date, default: Date.now },
+  },
+  { collection: "lot_bids" }
+);
+
 const itemSchema = new Schema<Item>({
   _id: { type: String, required: true },
   title: { type: String, required: true },
   description: { type: String, required: true },
   images: { type: [String], required: true },
   category: { type: String, required: true },
+  auction_start: { type: Date, required: false },
   auction_end: { type: Date, required: true },
-  base_price: { type: Number, required: true },
+  base_price: { type: moneySchema, required: true },
+  reserve_price: { type: moneySchema, required: false },
+  auction_type: { type: String, enum: ["ENGLISH", "DUTCH", "SEALED_FIRST_PRICE", "SEALED_SECOND_PRICE"], required: false },
+  clearing_price: { type: moneySchema, required: false },
+  quantity: { type: Number, required: false, default: 1 },
+  pending_transfers: { type: Number, required: false },
   rating: { type: String, enum: ["PENDING", "ONE", "TWO", "THREE", "FOUR", "FIVE"], required: true },
-  status: { type: String, enum: ["PENDING", "ACTIVE", "SOLD", "TRANSFERRING", "UNSOLD"], required: true },
+  status: { type: String, enum: ["PENDING", "SCHEDULED", "ACTIVE", "SOLD", "TRANSFERRING", "UNSOLD", "REMOVED"], required: true },
 });
 
 const BidModel: Model<Bid> = mongoose.model<Bid>("Bid", bidSchema);
 const ItemModel: Model<Item> = mongoose.model<Item>("Item", itemSchema);
+const LotBidModel: Model<LotBid> = mongoose.model<LotBid>("LotBid", lotBidSchema);
 
 async function connectToDatabase() {
   try {
@@ -61,9 +89,95 @@ async function checkIfItemIsSold(itemId: string): Promise<boolean> {
   return item.status === "SOLD";
 }
 
+async function getReservePrice(itemId: string): Promise<Money | undefined> {
+  const item = await ItemModel.findById(itemId);
+  return item?.reserve_price ?? undefined;
+}
+
+async function getItem(itemId: string): Promise<Item | null> {
+  return await ItemModel.findById(itemId);
+}
+
+function isSealedAuction(item: Item): boolean {
+  return item.auction_type === "SEALED_FIRST_PRICE" || item.auction_type === "SEALED_SECOND_PRICE";
+}
+
+// Ranks sealed bids highest first and works out what each bidder would pay if
+// they end up winning: their own bid for first-price auctions, or the next
+// highest bid (never below base/reserve price) for second-price auctions.
+async function getSealedBidRanking(item: Item, noOfBids = 5): Promise<RankedBid[]> {
+  const floor = item.reserve_price ? maxMoney(item.base_price, item.reserve_price) : item.base_price;
+  const bids = (await BidModel.find({ item_id: item._id }).sort({ "bid_price.minor_units": -1, timestamp: 1 }).exec()).filter(
+    (bid) => bid.bid_price.minor_units >= floor.minor_units
+  );
+  return bids.slice(0, noOfBids).map((bid, index) => ({
+    bidder: bid.bidder,
+    bid_price: item.auction_type === "SEALED_SECOND_PRICE" ? maxMoney(bids[index + 1]?.bid_price ?? floor, floor) : bid.bid_price,
+  }));
+}
+
+function isLot(item: Item): boolean {
+  return (item.quantity ?? 1) > 1;
+}
+
+// Fills the lot from the highest bids down (earliest update wins ties). The
+// last bid to receive units sets the uniform price every winner pays.
+async function getLotAllocation(item: Item): Promise<{ winners: LotWinner[]; clearingPrice?: Money }> {
+  const floor = item.reserve_price?.minor_units ?? 0;
+  const bids = (await LotBidModel.find({ item_id: item._id }).sort({ "bid_price.minor_units": -1, updated_at: 1 }).exec()).filter(
+    (bid) => bid.bid_price.minor_units >= floor
+  );
+  const winners: LotWinner[] = [];
+  let remaining = item.quantity ?? 1;
+  let clearingPrice: Money | undefined;
+  for (const bid of bids) {
+    if (remaining == 0) break;
+    const quantity = Math.min(bid.quantity, remaining);
+    winners.push({ bidder: bid.bidder, quantity });
+    remaining -= quantity;
+    clearingPrice = bid.bid_price;
+  }
+  return { winners, clearingPrice };
+}
+
+async function setClearingPrice(itemId: string, price: Money) {
+  await invalidateItemDetails(itemId);
+  await ItemModel.findByIdAndUpdate(itemId, { clearing_price: price });
+}
+
+// Lots stay TRANSFERRING until each winner has claimed their units.
+async function setPendingTransfers(itemId: string, count: number) {
+  await ItemModel.findByIdAndUpdate(itemId, { pending_transfers: count });
+}
+
+async function checkIfItemIsClosed(itemId: string): Promise<boolean> {
+  const item = await ItemModel.findById(itemId);
+  if (item === null) return true;
+  return ["TRANSFERRING", "SOLD", "UNSOLD", "REMOVED"].includes(item.status);
+}
+
 async function checkIfItemExists(itemId: string) {
   const item = await ItemModel.findById(itemId);
   return item != null;
 }
 
-export { checkIfItemExists, checkIfItemIsSold, connectToDatabase, getLatestBids, updateItemStatusToUnsold, updateItemStatusToTransferring, BidModel, ItemModel };
+export {
+  checkIfItemExists,
+  checkIfItemIsClosed,
+  checkIfItemIsSold,
+  connectToDatabase,
+  getItem,
+  getLatestBids,
+  getLotAllocation,
+  getReservePrice,
+  getSealedBidRanking,
+  isLot,
+  isSealedAuction,
+  setClearingPrice,
+  setPendingTransfers,
+  updateItemStatusToUnsold,
+  updateItemStatusToTransferring,
+  BidModel,
+  ItemModel,
+  LotBidModel,
+};
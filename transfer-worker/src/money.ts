@@ -0,0 +1,25 @@
+import { Money } from "./types";
+
+const ZERO_DECIMAL_CURRENCIES = ["BIF", "CLP", "DJF", "GNF", "ISK", "JPY", "KMF", "KRW", "PYG", "RWF", "UGX", "VND", "VUV", "XAF", "XOF", "XPF"];
+const THREE_DECIMAL_CURRENCIES = ["BHD", "IQD", "JOD", "KWD", "LYD", "OMR", "TND"];
+
+function currencyExponent(currency: string): number {
+  if (ZERO_DECIMAL_CURRENCIES.includes(currency)) return 0;
+  if (THREE_DECIMAL_CURRENCIES.includes(currency)) return 3;
+  return 2;
+}
+
+// Exact decimal string for an amount, matching how the server formats prices.
+function formatMoney(money: Money): string {
+  const exponent = currencyExponent(money.currency);
+  const sign = money.minor_units < 0 ? "-" : "";
+  const digits = Math.abs(money.minor_units).toString().padStart(exponent + 1, "0");
+  if (exponent == 0) return sign + digits;
+  return `${sign}${digits.slice(0, -exponent)}.${digits.slice(-exponent)}`;
+}
+
+function maxMoney(a: Money, b: Money): Money {
+  return b.minor_units > a.minor_units ? b : a;
+}
+
+export { formatMoney, maxMoney };
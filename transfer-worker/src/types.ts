@@ -13,6 +13,7 @@ export type transferQueueElement =
       item_id: string;
       user_id: string;
       price: string;
+      currency: string;
       prev_user_id: string;
       seller: string;
     }
@@ -27,15 +28,19 @@ export type messageToPublish = {
   item_id: string;
   user_id: string;
   price: string;
+  currency: string;
   item_name: string;
   seller: string;
+  quantity?: number;
 };
 
 export type itemDetails = {
   item_id: string;
   item_name: string;
   price: string;
+  currency: string;
   seller: string;
+  quantity?: number;
 };
 
 export type transferSchedulerData =
@@ -45,6 +50,7 @@ export type transferSchedulerData =
       item_id: string;
       user_id: string;
       price: string;
+      currency: string;
       prev_user_id: string;
       seller: string;
       delay: number;
@@ -56,13 +62,37 @@ export type transferSchedulerData =
       delay: number;
     };
 
+export type Money = {
+  minor_units: number;
+  currency: string;
+};
+
+export type RankedBid = {
+  bidder: string;
+  bid_price: Money;
+};
+
 export interface Bid extends Document {
-  bid_price: number;
+  bid_price: Money;
   bidder: string;
   item_id: string;
   timestamp: Date;
 }
 
+export interface LotBid extends Document {
+  item_id: string;
+  bidder: string;
+  quantity: number;
+  bid_price: Money;
+  created_at: Date;
+  updated_at: Date;
+}
+
+export type LotWinner = {
+  bidder: string;
+  quantity: number;
+};
+
 export interface Item extends Document {
   _id: string;
   title: string;
@@ -72,5 +102,10 @@ export interface Item extends Document {
   auction_end: Date;
   rating: string;
   status: string;
-  base_price: number;
+  base_price: Money;
+  reserve_price?: Money;
+  auction_type?: string;
+  clearing_price?: Money;
+  quantity?: number;
+  pending_transfers?: number;
 }
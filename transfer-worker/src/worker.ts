@@ -1,7 +1,23 @@
 import { scheduleTransfer } from "./axios";
-import { checkIfItemExists, checkIfItemIsSold, getLatestBids, updateItemStatusToTransferring, updateItemStatusToUnsold } from "./db";
+import {
+  checkIfItemExists,
+  checkIfItemIsClosed,
+  checkIfItemIsSold,
+  getItem,
+  getLatestBids,
+  getLotAllocation,
+  getReservePrice,
+  getSealedBidRanking,
+  isLot,
+  isSealedAuction,
+  setClearingPrice,
+  setPendingTransfers,
+  updateItemStatusToTransferring,
+  updateItemStatusToUnsold,
+} from "./db";
 import { addItemToUserList, invalidateItemsCurrentBid, publish, removeItemFromSimilarItems, removeItemFromUserList } from "./redis";
-import { transferQueueElement, Bid } from "./types";
+import { formatMoney } from "./money";
+import { transferQueueElement, Item, RankedBid } from "./types";
 
 class TransferWorker {
   private static instance: TransferWorker;
@@ -16,6 +32,7 @@ class TransferWorker {
   public async process(dequedElement: transferQueueElement) {
     if (dequedElement?.type == 1) {
       if (!(await checkIfItemExists(dequedElement.item_id))) return;
+      if (await checkIfItemIsClosed(dequedElement.item_id)) return;
       await this.handleFirstBid(dequedElement);
     } else if (dequedElement?.type == 2) {
       if (await checkIfItemIsSold(dequedElement.item_id)) return;
@@ -33,35 +50,76 @@ class TransferWorker {
   }
 
   private async handleReminingBids(dequedElement: transferQueueElement) {
-    const { item_id, user_id, price, prev_user_id, item_name, seller } =
-      dequedElement?.type == 2 ? dequedElement : { item_id: "", user_id: "", price: "", prev_user_id: "", item_name: "", seller: "" };
+    const { item_id, user_id, price, currency, prev_user_id, item_name, seller } =
+      dequedElement?.type == 2
+        ? dequedElement
+        : { item_id: "", user_id: "", price: "", currency: "", prev_user_id: "", item_name: "", seller: "" };
 
     await removeItemFromUserList(prev_user_id, item_id);
-    await addItemToUserList(user_id, { item_id, price, item_name, seller });
-    await publish("transfer", { item_id, user_id, price, item_name, seller });
+    await addItemToUserList(user_id, { item_id, price, currency, item_name, seller });
+    await publish("transfer", { item_id, user_id, price, currency, item_name, seller });
   }
 
   private async handleFirstBid(dequedElement: transferQueueElement) {
     const { item_name, item_id, seller } = dequedElement?.type == 1 ? dequedElement : { item_name: "", item_id: "", seller: "" };
     await invalidateItemsCurrentBid(item_id);
     await removeItemFromSimilarItems(item_id);
-    const latestFiveBids = await getLatestBids(item_id, 5);
+    const item = await getItem(item_id);
+    if (item !== null && isLot(item)) {
+      await this.handleLotClose(item, item_name, seller);
+      return;
+    }
+    const latestFiveBids = await this.getWinningBids(item_id);
     await this.handleDBStatus(latestFiveBids, item_id);
     if (latestFiveBids.length == 0) {
       return;
     }
-    await addItemToUserList(latestFiveBids[0].bidder, { item_id, price: latestFiveBids[0].bid_price.toString(), item_name, seller });
+    const price = formatMoney(latestFiveBids[0].bid_price);
+    const currency = latestFiveBids[0].bid_price.currency;
+    await addItemToUserList(latestFiveBids[0].bidder, { item_id, price, currency, item_name, seller });
     await publish("transfer", {
       item_id,
       user_id: latestFiveBids[0].bidder,
-      price: latestFiveBids[0].bid_price.toString(),
+      price,
+      currency,
       item_name,
       seller,
     });
     await this.registerRemainingBids(latestFiveBids, item_id, item_name, seller);
   }
 
-  private async registerRemainingBids(latestFiveBids: Bid[], item_id: string, item_name: string, seller: string) {
+  private async handleLotClose(item: Item, item_name: string, seller: string) {
+    const item_id = item._id;
+    const { winners, clearingPrice } = await getLotAllocation(item);
+    if (winners.length == 0 || clearingPrice === undefined) {
+      await updateItemStatusToUnsold(item_id);
+      return;
+    }
+    await setClearingPrice(item_id, clearingPrice);
+    await setPendingTransfers(item_id, winners.length);
+    await updateItemStatusToTransferring(item_id);
+    const price = formatMoney(clearingPrice);
+    const currency = clearingPrice.currency;
+    for (const { bidder, quantity } of winners) {
+      await addItemToUserList(bidder, { item_id, price, currency, item_name, seller, quantity });
+      await publish("transfer", { item_id, user_id: bidder, price, currency, item_name, seller, quantity });
+    }
+  }
+
+  private async getWinningBids(item_id: string): Promise<RankedBid[]> {
+    const item = await getItem(item_id);
+    if (item !== null && isSealedAuction(item)) {
+      const rankedBids = await getSealedBidRanking(item, 5);
+      if (rankedBids.length > 0) {
+        await setClearingPrice(item_id, rankedBids[0].bid_price);
+      }
+      return rankedBids;
+    }
+    const reservePrice = await getReservePrice(item_id);
+    return (await getLatestBids(item_id, 5)).filter((bid) => reservePrice === undefined || bid.bid_price.minor_units >= reservePrice.minor_units);
+  }
+
+  private async registerRemainingBids(latestFiveBids: RankedBid[], item_id: string, item_name: string, seller: string) {
     const FIVE_MINUTES = 5 * 60 * 1000;
     let time_index = 1;
     for (let i = 1; i < Math.min(latestFiveBids.length, 5); i++) {
@@ -70,7 +128,8 @@ class TransferWorker {
         item_id,
         item_name,
         user_id: latestFiveBids[i].bidder,
-        price: latestFiveBids[i].bid_price.toString(),
+        price: formatMoney(latestFiveBids[i].bid_price),
+        currency: latestFiveBids[i].bid_price.currency,
         prev_user_id: latestFiveBids[i - 1].bidder,
         delay: FIVE_MINUTES * time_index++,
         seller,
@@ -80,7 +139,7 @@ class TransferWorker {
     await scheduleTransfer({ type: 3, item_id, prev_user_id: latestFiveBids[lastValidBidIndex].bidder, delay: FIVE_MINUTES * time_index });
   }
 
-  private async handleDBStatus(latestFiveBids: Bid[], item_id: string) {
+  private async handleDBStatus(latestFiveBids: RankedBid[], item_id: string) {
     if (latestFiveBids.length == 0) {
       await updateItemStatusToUnsold(item_id);
     } else {
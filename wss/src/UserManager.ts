@@ -8,6 +8,7 @@ type itemDetails = {
   item_id: string;
   item_name: string;
   price: string;
+  currency?: string;
   seller: string;
 };
 
@@ -4,12 +4,21 @@ type TransferMessages = {
   item_id: string;
   item_name: string;
   price: string;
+  currency?: string;
   seller: string;
 };
 
 type BidUpdateMessages = {
   type: "BIDUPDATE";
   price: string;
+  currency?: string;
+  auction_end: string;
+  version?: number;
+  item_id?: string;
+  bidder_alias?: string;
+  bid_count?: number;
+  sequence?: number;
+  server_timestamp?: string;
 };
 
 export type OutgoingMessage = TransferMessages | BidUpdateMessages;